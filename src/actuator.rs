@@ -0,0 +1,33 @@
+/// The hardware, simulator or mock that a propulsion fire drives.
+///
+/// A `CommandProcessor` calls `fire` at the moment a scheduled fire ignites,
+/// before the fire's telemetry is sent.
+pub trait PropulsionActuator: Send + Sync {
+    /// Ignites the propulsion of a stage.
+    ///
+    /// # Arguments
+    ///
+    /// * `stage` - The name of the propulsion stage to fire.
+    fn fire(&self, stage: &str);
+
+    /// Reports the actuator's current hardware fault, if any.
+    ///
+    /// A `CommandProcessor` checks this before each schedule and on every
+    /// tick; while a fault is latched it rejects new schedules. Actuators that
+    /// cannot detect faults keep the default, which is always healthy.
+    ///
+    /// # Returns
+    ///
+    /// A description of the fault, or `None` while the actuator is healthy.
+    fn fault(&self) -> Option<String> {
+        None
+    }
+}
+
+/// An actuator that does nothing, so a fire only produces telemetry.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopActuator;
+
+impl PropulsionActuator for NoopActuator {
+    fn fire(&self, _stage: &str) {}
+}
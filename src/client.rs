@@ -0,0 +1,255 @@
+use crate::command::{Command, CommandOutcome, DEFAULT_STAGE, DelayUnit, FireTime};
+use crate::errors::FlightComputerError;
+use crate::telemetry::{Severity, TelemetryMessage};
+use std::io;
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines},
+    net::{
+        TcpStream, ToSocketAddrs,
+        tcp::{OwnedReadHalf, OwnedWriteHalf},
+    },
+};
+
+/// A typed client for a running `FlightComputer`, for tests and embedding.
+///
+/// The client holds one connection to the command port, on which each command
+/// is sent as a JSON line and answered with its `CommandOutcome`, and one to
+/// the telemetry port, from which telemetry lines are read and parsed.
+pub struct FlightComputerClient {
+    /// The lines arriving on the command connection.
+    acks: Lines<BufReader<OwnedReadHalf>>,
+    /// The sending half of the command connection.
+    commands: OwnedWriteHalf,
+    /// The lines arriving on the telemetry connection.
+    telemetry: Lines<BufReader<TcpStream>>,
+}
+
+impl FlightComputerClient {
+    /// Connects to a flight computer's command and telemetry ports.
+    ///
+    /// # Arguments
+    ///
+    /// * `command_addr` - The address of the command listener.
+    /// * `log_addr` - The address of the telemetry listener.
+    ///
+    /// # Returns
+    ///
+    /// The connected client, or the `FlightComputerError::Io` if either
+    /// connection could not be made.
+    pub async fn connect(
+        command_addr: impl ToSocketAddrs,
+        log_addr: impl ToSocketAddrs,
+    ) -> Result<Self, FlightComputerError> {
+        let (reader, commands) = TcpStream::connect(command_addr).await?.into_split();
+        let telemetry = TcpStream::connect(log_addr).await?;
+        Ok(Self {
+            acks: BufReader::new(reader).lines(),
+            commands,
+            telemetry: BufReader::new(telemetry).lines(),
+        })
+    }
+
+    /// Presents the shared secret the flight computer's `auth_token` requires.
+    ///
+    /// This must be the first thing sent on a computer that requires
+    /// authentication; a rejection closes the connection.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - The shared secret.
+    pub async fn authenticate(
+        &mut self,
+        token: &str,
+    ) -> Result<CommandOutcome, FlightComputerError> {
+        let line = serde_json::json!({ "auth": token }).to_string();
+        self.send_line(&line).await
+    }
+
+    /// Schedules an instantaneous fire of the default stage.
+    ///
+    /// # Arguments
+    ///
+    /// * `delay` - The delay in seconds before firing.
+    pub async fn schedule(&mut self, delay: f64) -> Result<CommandOutcome, FlightComputerError> {
+        self.send(&Command::Schedule {
+            when: FireTime::Delay(delay),
+            unit: DelayUnit::Seconds,
+            stage: DEFAULT_STAGE.to_string(),
+            burn: 0.0,
+            priority: 0,
+        })
+        .await
+    }
+
+    /// Cancels every pending fire on every stage.
+    pub async fn cancel(&mut self) -> Result<CommandOutcome, FlightComputerError> {
+        self.send(&Command::Cancel {
+            id: None,
+            stage: None,
+        })
+        .await
+    }
+
+    /// Requests a `📋` status report, which arrives as telemetry.
+    pub async fn status(&mut self) -> Result<CommandOutcome, FlightComputerError> {
+        self.send(&Command::Status).await
+    }
+
+    /// Sends any command and waits for its acknowledgement.
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - The command to send.
+    ///
+    /// # Returns
+    ///
+    /// The outcome the flight computer acknowledged, or a `FlightComputerError`
+    /// if the connection failed or the acknowledgement could not be parsed.
+    pub async fn send(&mut self, command: &Command) -> Result<CommandOutcome, FlightComputerError> {
+        let line = serde_json::to_string(command)?;
+        self.send_line(&line).await
+    }
+
+    /// Reads and parses the next telemetry line.
+    ///
+    /// Both telemetry formats are understood. Text lines carry no severity, so
+    /// their messages are reported as `Severity::Info`.
+    ///
+    /// # Returns
+    ///
+    /// The next message, `None` once the flight computer closes the telemetry
+    /// connection, or a `FlightComputerError` if the connection failed or the
+    /// line could not be parsed.
+    pub async fn next_telemetry(
+        &mut self,
+    ) -> Result<Option<TelemetryMessage>, FlightComputerError> {
+        let Some(line) = self.telemetry.next_line().await? else {
+            return Ok(None);
+        };
+        if line.starts_with('{') {
+            return Ok(Some(serde_json::from_str(&line)?));
+        }
+        parse_text_line(&line).map(Some).ok_or_else(|| {
+            FlightComputerError::Io(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("malformed telemetry line: {}", line),
+            ))
+        })
+    }
+
+    /// Sends one line on the command connection and reads its acknowledgement,
+    /// skipping the echo a computer with `echo_commands` sends before it.
+    async fn send_line(&mut self, line: &str) -> Result<CommandOutcome, FlightComputerError> {
+        self.commands
+            .write_all(format!("{}\n", line).as_bytes())
+            .await?;
+        loop {
+            let Some(ack) = self.acks.next_line().await? else {
+                return Err(FlightComputerError::Io(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "command connection closed before the acknowledgement",
+                )));
+            };
+            let echoed = serde_json::from_str::<serde_json::Value>(&ack)
+                .is_ok_and(|value| value.get("echo").is_some());
+            if !echoed {
+                return Ok(serde_json::from_str(&ack)?);
+            }
+        }
+    }
+}
+
+/// Parses a text-format telemetry line, `[<ts> ][#<seq> ][<tag>] <payload>`.
+///
+/// A line without a sequence number is reported with a `seq` of `0`.
+fn parse_text_line(line: &str) -> Option<TelemetryMessage> {
+    let (prefix, rest) = line.split_once('[')?;
+    let (tag, payload) = rest.split_once("] ")?;
+    let mut ts = None;
+    let mut seq = 0;
+    for field in prefix.split_whitespace() {
+        match field.strip_prefix('#') {
+            Some(number) => seq = number.parse().ok()?,
+            None => ts = Some(field.to_string()),
+        }
+    }
+    Some(TelemetryMessage {
+        seq,
+        ts,
+        severity: Severity::Info,
+        tag: tag.to_string(),
+        payload: payload.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::flight_computer::FlightComputer;
+    use std::time::Duration;
+    use tokio::time;
+
+    /// Reads telemetry until a message with `tag` arrives, and returns it.
+    async fn next_tagged(client: &mut FlightComputerClient, tag: &str) -> TelemetryMessage {
+        let read = async {
+            loop {
+                let message = client.next_telemetry().await.unwrap().unwrap();
+                if message.tag == tag {
+                    return message;
+                }
+            }
+        };
+        time::timeout(Duration::from_secs(1), read)
+            .await
+            .expect("timed out waiting for telemetry")
+    }
+
+    #[tokio::test]
+    async fn client_drives_a_running_computer() {
+        let handle = FlightComputer::builder()
+            .command_port(0)
+            .log_port(0)
+            .build()
+            .start()
+            .await
+            .unwrap();
+        let mut client = FlightComputerClient::connect(handle.command_addr(), handle.log_addr())
+            .await
+            .unwrap();
+        assert_eq!(client.next_telemetry().await.unwrap().unwrap().tag, "🚦");
+
+        let scheduled = client.schedule(0.1).await.unwrap();
+        assert_eq!(scheduled, CommandOutcome::Accepted { id: Some(1) });
+        let fire = next_tagged(&mut client, "🚀").await;
+        assert!(fire.payload.starts_with("Firing propulsion 'main' now!"));
+
+        client.schedule(60.0).await.unwrap();
+        assert_eq!(client.cancel().await.unwrap(), CommandOutcome::accepted());
+        client.status().await.unwrap();
+        let status = next_tagged(&mut client, "📋").await;
+        assert!(
+            status.payload.starts_with("Status: idle"),
+            "{}",
+            status.payload
+        );
+
+        handle.shutdown().await.unwrap();
+        let closed = async { while client.next_telemetry().await.unwrap().is_some() {} };
+        time::timeout(Duration::from_secs(1), closed)
+            .await
+            .expect("telemetry connection was not closed");
+    }
+
+    #[test]
+    fn text_lines_parse_with_or_without_prefixes() {
+        let full = parse_text_line("2024-05-01T12:34:56.789Z #42 [🚀] Firing now!").unwrap();
+        assert_eq!(full.ts.as_deref(), Some("2024-05-01T12:34:56.789Z"));
+        assert_eq!((full.seq, full.tag.as_str()), (42, "🚀"));
+        assert_eq!(full.payload, "Firing now!");
+
+        let bare = parse_text_line("[📋] Status: idle").unwrap();
+        assert_eq!((bare.seq, bare.ts), (0, None));
+        assert_eq!(bare.payload, "Status: idle");
+        assert!(parse_text_line("no tag here").is_none());
+    }
+}
@@ -0,0 +1,78 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::time::Instant;
+
+/// The source of the current time that a `CommandProcessor` schedules against.
+pub trait Clock: Send + Sync {
+    /// Returns the current time.
+    fn now(&self) -> Instant;
+}
+
+/// The real clock, reading `tokio::time::Instant::now`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokioClock;
+
+impl Clock for TokioClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// A clock that only moves when advanced, for deterministic tests.
+///
+/// Clones share the same time, so a test can keep one clone to advance while
+/// a `CommandProcessor` reads another.
+#[derive(Debug, Clone)]
+pub struct MockClock {
+    /// The current time, shared by every clone.
+    now: Arc<Mutex<Instant>>,
+}
+
+impl Default for MockClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MockClock {
+    /// Creates a mock clock stopped at the real current time.
+    pub fn new() -> Self {
+        Self {
+            now: Arc::new(Mutex::new(Instant::now())),
+        }
+    }
+
+    /// Moves the clock forward.
+    ///
+    /// # Arguments
+    ///
+    /// * `by` - How far to advance the clock.
+    pub fn advance(&self, by: Duration) {
+        *self.now.lock().expect("mock clock lock poisoned") += by;
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> Instant {
+        *self.now.lock().expect("mock clock lock poisoned")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mock_clock_clones_advance_together() {
+        let clock = MockClock::new();
+        let reader = clock.clone();
+        let start = reader.now();
+
+        clock.advance(Duration::from_millis(1500));
+
+        assert_eq!(reader.now() - start, Duration::from_millis(1500));
+        assert_eq!(clock.now(), reader.now());
+    }
+}
@@ -1,90 +1,2900 @@
-use crate::telemetry::TelemetryHub;
-use serde::{Deserialize, Serialize};
-use std::time::Duration;
-use tokio::time::Instant;
+use crate::actuator::{NoopActuator, PropulsionActuator};
+use crate::clock::{Clock, TokioClock};
+use crate::errors::FlightComputerError;
+use crate::telemetry::{Severity, TelemetryHub, TelemetryTags};
+use crate::thrust::ThrustProfile;
+use crate::{info, warn};
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error as _};
+use std::{
+    cmp::Reverse,
+    collections::VecDeque,
+    io,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::{fs, sync::Notify, time::Instant};
 
-/// Represents a command with a delay in seconds.
+/// Represents a command sent to the flight computer.
+///
+/// Commands are JSON objects tagged by `type`, e.g. `{"type":"schedule","delay":2.0}`.
+/// The legacy forms are still accepted when deserializing: a bare delay such
+/// as `2.0` (or `-1` to cancel everything), `{"delay": 2.0, "burn": 1.5}`, and
+/// `{"cancel": 7}`.
+///
+/// Unknown fields of commands that take any are rejected, so that a misspelt
+/// field such as `"stgae"` cannot silently fall back to its default and fire
+/// a different stage.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(
+    remote = "Self",
+    tag = "type",
+    rename_all = "snake_case",
+    deny_unknown_fields
+)]
+pub enum Command {
+    /// Schedules a fire of `stage` at `when` that burns for `burn` seconds.
+    Schedule {
+        /// When the propulsion fires, given as either a `delay` or an `at_ms` field.
+        #[serde(flatten)]
+        when: FireTime,
+        /// The unit a `delay` is given in, seconds when omitted.
+        #[serde(default, skip_serializing_if = "DelayUnit::is_seconds")]
+        unit: DelayUnit,
+        /// The propulsion stage to fire, `DEFAULT_STAGE` when omitted.
+        #[serde(default = "default_stage", skip_serializing_if = "is_default_stage")]
+        stage: String,
+        /// The burn duration in seconds, instantaneous when omitted.
+        #[serde(default, skip_serializing_if = "is_zero")]
+        burn: f64,
+        /// Orders fires with the same deadline, highest first, `0` when omitted.
+        #[serde(default, skip_serializing_if = "is_lowest_priority")]
+        priority: u8,
+    },
+    /// Cancels the pending fire with the given id, or every fire on `stage` when
+    /// `id` is absent.
+    ///
+    /// A cancel without a stage cancels every stage, so the legacy cancel-all
+    /// still stops all propulsion. Ids are unique across stages, so `stage` is
+    /// ignored when `id` is given.
+    Cancel {
+        /// The id reported when the fire was scheduled.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        id: Option<u64>,
+        /// The stage to cancel, or every stage when absent.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        stage: Option<String>,
+    },
+    /// Cancels every pending fire, aborts any burn in progress, and locks out
+    /// new fires until a `Clear`.
+    Abort,
+    /// Clears the abort latch so fires can be scheduled again.
+    Clear,
+    /// Clears a latched actuator fault so fires can be scheduled again.
+    ClearFault,
+    /// Holds every pending fire without cancelling it, until a `Resume`.
+    Pause,
+    /// Releases a `Pause`, re-arming every held fire.
+    Resume,
+    /// Reports the currently pending fires and active burns.
+    Status,
+    /// Returns the processor to idle: cancels every fire and burn, and clears
+    /// the pause, abort and fault latches, keeping its configuration.
+    Reset {
+        /// Whether the metrics counters are zeroed too, `false` when omitted.
+        #[serde(default, skip_serializing_if = "is_false")]
+        clear_metrics: bool,
+    },
+}
+
+// `remote = "Self"` turns the derives above into inherent functions, so the
+// trait impls can add the legacy fallback while reusing the tagged format.
+impl Serialize for Command {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Command::serialize(self, serializer)
+    }
+}
+
+// The wire form is picked from the shape of the value rather than by trying
+// each form in turn, so a malformed command reports its own form's error.
+impl<'de> Deserialize<'de> for Command {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        let legacy = match &value {
+            serde_json::Value::Number(delay) => match delay.as_f64() {
+                Some(-1.0) => {
+                    return Ok(Command::Cancel {
+                        id: None,
+                        stage: None,
+                    });
+                }
+                Some(delay) => LegacyBurn { delay, burn: 0.0 },
+                None => return Err(D::Error::custom("delay is not a finite number")),
+            },
+            serde_json::Value::Object(fields) if fields.contains_key("type") => {
+                return Command::deserialize(value).map_err(D::Error::custom);
+            }
+            serde_json::Value::Object(fields) if fields.contains_key("cancel") => {
+                let LegacyCancel { cancel } =
+                    LegacyCancel::deserialize(value).map_err(D::Error::custom)?;
+                return Ok(Command::Cancel {
+                    id: Some(cancel),
+                    stage: None,
+                });
+            }
+            serde_json::Value::Object(_) => {
+                LegacyBurn::deserialize(value).map_err(D::Error::custom)?
+            }
+            _ => {
+                return Err(D::Error::custom(
+                    "expected a command object or a bare delay",
+                ));
+            }
+        };
+        Ok(Command::Schedule {
+            when: FireTime::Delay(legacy.delay),
+            unit: DelayUnit::Seconds,
+            stage: default_stage(),
+            burn: legacy.burn,
+            priority: 0,
+        })
+    }
+}
+
+/// When a scheduled fire ignites.
+///
+/// On the wire exactly one of `"delay": 2.0` or `"at_ms": 1714566896789` is given.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FireTime {
+    /// A delay from when the command is handled, in the schedule's `unit`.
+    Delay(f64),
+    /// An absolute wall-clock time, in milliseconds since the Unix epoch.
+    AtMs(u64),
+}
+
+impl<'de> Deserialize<'de> for FireTime {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        /// Both fire time fields, so that giving both or neither can be rejected.
+        #[derive(Deserialize)]
+        struct FireTimeFields {
+            delay: Option<f64>,
+            at_ms: Option<u64>,
+        }
+
+        match FireTimeFields::deserialize(deserializer)? {
+            FireTimeFields {
+                delay: Some(delay),
+                at_ms: None,
+            } => Ok(Self::Delay(delay)),
+            FireTimeFields {
+                delay: None,
+                at_ms: Some(at_ms),
+            } => Ok(Self::AtMs(at_ms)),
+            FireTimeFields { delay: Some(_), .. } => Err(serde::de::Error::custom(
+                "`delay` and `at_ms` are mutually exclusive",
+            )),
+            FireTimeFields { .. } => {
+                Err(serde::de::Error::custom("missing field `delay` or `at_ms`"))
+            }
+        }
+    }
+}
+
+/// The unit a schedule's delay is given in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum DelayUnit {
+    /// Seconds, `"s"`.
+    #[default]
+    #[serde(rename = "s")]
+    Seconds,
+    /// Milliseconds, `"ms"`.
+    #[serde(rename = "ms")]
+    Milliseconds,
+    /// Minutes, `"min"`.
+    #[serde(rename = "min")]
+    Minutes,
+}
+
+impl DelayUnit {
+    /// Converts a value in this unit to seconds.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to convert.
+    pub fn to_secs(self, value: f64) -> f64 {
+        match self {
+            Self::Seconds => value,
+            Self::Milliseconds => value / 1000.0,
+            Self::Minutes => value * 60.0,
+        }
+    }
+
+    /// Returns whether this is the default unit, so it can be omitted when serializing.
+    fn is_seconds(&self) -> bool {
+        *self == Self::Seconds
+    }
+}
+
+/// The legacy `{"delay": 2.0, "burn": 1.5}` schedule form.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LegacyBurn {
+    delay: f64,
+    #[serde(default)]
+    burn: f64,
+}
+
+/// The legacy `{"cancel": 7}` cancel-by-id form.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LegacyCancel {
+    cancel: u64,
+}
+
+/// The propulsion stage a schedule targets when it names none.
+pub const DEFAULT_STAGE: &str = "main";
+
+/// Returns `DEFAULT_STAGE` as an owned stage name, for serde defaults.
+fn default_stage() -> String {
+    DEFAULT_STAGE.to_string()
+}
+
+/// Returns whether a stage is `DEFAULT_STAGE`, so it can be omitted when serializing.
+fn is_default_stage(stage: &str) -> bool {
+    stage == DEFAULT_STAGE
+}
+
+/// Returns whether a burn duration is zero, so it can be omitted when serializing.
+fn is_zero(value: &f64) -> bool {
+    *value == 0.0
+}
+
+/// Returns whether a flag is unset, so it can be omitted when serializing.
+fn is_false(flag: &bool) -> bool {
+    !*flag
+}
+
+/// Returns whether a priority is the lowest, so it can be omitted when serializing.
+fn is_lowest_priority(priority: &u8) -> bool {
+    *priority == 0
+}
+
+/// The default shortest accepted fire delay, in seconds.
+pub const DEFAULT_MIN_DELAY_SECS: f64 = 0.0;
+
+/// The default longest accepted fire delay, in seconds.
+pub const DEFAULT_MAX_DELAY_SECS: f64 = 3600.0;
+
+/// The default mission window past which no fire may be scheduled, unbounded.
+pub const DEFAULT_MAX_HORIZON: Duration = Duration::MAX;
+
+/// The suggested deadline difference within which `with_dedupe_window`
+/// coalesces an identical schedule into a pending fire.
+pub const DEFAULT_DEDUPE_WINDOW: Duration = Duration::from_millis(100);
+
+/// The number of seconds before ignition at which the countdown starts.
+const COUNTDOWN_FROM_SECS: u64 = 5;
+
+/// Fires scheduled closer than this many seconds out get no countdown.
+const COUNTDOWN_MIN_DELAY_SECS: f64 = 2.0;
+
+/// The result of handling a command, acknowledged back to its sender.
+///
+/// Serializes as e.g. `{"status":"accepted","id":7}` or
+/// `{"status":"rejected","reason":"Invalid delay value: -5"}`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum CommandOutcome {
+    /// The command was carried out.
+    Accepted {
+        /// The id of the fire the command scheduled or cancelled, if any.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        id: Option<u64>,
+    },
+    /// The command was not carried out.
+    Rejected {
+        /// A description of why the command was rejected.
+        reason: String,
+    },
+}
+
+impl CommandOutcome {
+    /// An accepted outcome that does not refer to a particular fire.
+    pub fn accepted() -> Self {
+        Self::Accepted { id: None }
+    }
+}
+
+/// A snapshot of the counters a `CommandProcessor` keeps over its lifetime.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ProcessorMetrics {
+    /// Every command handled, whatever its outcome.
+    pub commands_received: u64,
+    /// Schedules that armed a fire.
+    pub schedules_accepted: u64,
+    /// Cancels that were carried out, whether of one fire or every fire.
+    pub cancels: u64,
+    /// Commands rejected as invalid.
+    pub invalid_commands: u64,
+    /// Propulsion fires carried out.
+    pub fires_executed: u64,
+}
+
+/// The number of handled commands kept in a processor's history.
+const HISTORY_CAPACITY: usize = 256;
+
+/// A handled command, as recorded in a processor's history.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HistoryEntry {
+    /// When the command was handled.
+    pub at: SystemTime,
+    /// The command as received.
+    pub command: Command,
+    /// The outcome acknowledged to the sender.
+    pub outcome: CommandOutcome,
+}
+
+/// A propulsion event waiting for its ignition time.
+#[derive(Debug, Clone)]
+struct ScheduledFire {
+    /// The id assigned when the fire was scheduled.
+    id: u64,
+    /// The propulsion stage that fires.
+    stage: String,
+    /// The priority ordering this fire among fires with the same deadline.
+    priority: u8,
+    /// The time at which the propulsion fires.
+    deadline: Instant,
+    /// How long the propulsion burns once fired.
+    burn: Duration,
+    /// The next whole number of seconds before ignition to announce, `0` once
+    /// the countdown is finished or when the fire has none.
+    countdown: u64,
+}
+
+impl ScheduledFire {
+    /// Returns the time at which the next countdown mark is due, if any.
+    fn next_countdown(&self) -> Option<Instant> {
+        (self.countdown > 0).then(|| self.deadline - Duration::from_secs(self.countdown))
+    }
+}
+
+/// A burn that has ignited and not yet completed.
+#[derive(Debug, Clone)]
+struct ActiveBurn {
+    /// The id of the fire that started the burn.
+    id: u64,
+    /// The propulsion stage that is burning.
+    stage: String,
+    /// The time at which the burn ignited.
+    start: Instant,
+    /// The time at which the burn completes.
+    end: Instant,
+    /// The time the next thrust sample is due, `None` once the final sample
+    /// at the end of the burn has been sent.
+    next_sample: Option<Instant>,
+}
+
+/// The live state of a `CommandProcessor`, with every deadline stored as the
+/// time remaining when the snapshot was taken.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ProcessorSnapshot {
+    /// The id the next scheduled fire receives.
+    pub next_id: u64,
+    /// The pending fires, earliest first.
+    pub fires: Vec<FireSnapshot>,
+    /// The burns in progress, earliest completion first.
+    pub burns: Vec<BurnSnapshot>,
+    /// Whether the abort latch is set.
+    pub aborted: bool,
+    /// Whether scheduling is paused, in which case fire times are as of the pause.
+    pub paused: bool,
+    /// The lifetime command and fire counters.
+    pub metrics: ProcessorMetrics,
+}
+
+/// A pending fire in a `ProcessorSnapshot`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct FireSnapshot {
+    /// The id assigned when the fire was scheduled.
+    pub id: u64,
+    /// The propulsion stage that fires.
+    pub stage: String,
+    /// The seconds remaining until ignition.
+    pub remaining_secs: f64,
+    /// How long the propulsion burns once fired, in seconds.
+    pub burn_secs: f64,
+    /// The priority ordering this fire among fires with the same deadline.
+    #[serde(default)]
+    pub priority: u8,
+}
+
+/// A burn in progress in a `ProcessorSnapshot`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct BurnSnapshot {
+    /// The id of the fire that started the burn.
+    pub id: u64,
+    /// The propulsion stage that is burning.
+    pub stage: String,
+    /// The seconds since the burn ignited.
+    #[serde(default)]
+    pub elapsed_secs: f64,
+    /// The seconds remaining until the burn completes.
+    pub remaining_secs: f64,
+}
+
+/// The on-disk form of a processor's pending schedule.
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct PersistedSchedule {
+    /// The id the next scheduled fire receives, so ids stay unique across restarts.
+    next_id: u64,
+    /// The pending fires, earliest first.
+    fires: Vec<PersistedFire>,
+}
+
+/// The on-disk form of a pending fire.
 #[derive(Debug, Deserialize, Serialize)]
-pub struct Command(pub f64);
+struct PersistedFire {
+    /// The id assigned when the fire was scheduled.
+    id: u64,
+    /// The propulsion stage that fires.
+    #[serde(default = "default_stage")]
+    stage: String,
+    /// The wall-clock ignition time, in milliseconds since the Unix epoch.
+    fire_at_ms: u64,
+    /// How long the propulsion burns once fired, in seconds.
+    burn_secs: f64,
+    /// The priority ordering this fire among fires with the same deadline.
+    #[serde(default)]
+    priority: u8,
+}
 
 /// Processes commands and manages scheduled propulsion events.
 #[derive(Clone)]
 pub struct CommandProcessor {
-    /// The time at which the propulsion is scheduled to fire.
-    scheduled_fire: Option<Instant>,
+    /// The propulsion events waiting to fire, earliest first.
+    scheduled_fires: Vec<ScheduledFire>,
+    /// The burns currently in progress, earliest completion first.
+    active_burns: Vec<ActiveBurn>,
+    /// The id assigned to the next scheduled fire.
+    next_id: u64,
+    /// The shortest accepted fire delay, in seconds.
+    min_delay: f64,
+    /// The longest accepted fire delay, in seconds.
+    max_delay: f64,
+    /// How far past now a fire's deadline may fall.
+    max_horizon: Duration,
+    /// How overdue a fire may be and still ignite, or `None` to always fire late.
+    stale_tolerance: Option<Duration>,
+    /// How close an identical schedule's deadline must be to a pending fire's
+    /// to be coalesced into it, or `None` to never coalesce.
+    dedupe_window: Option<Duration>,
+    /// The file the pending schedule is saved to, if persistence is enabled.
+    persistence_path: Option<PathBuf>,
     /// The telemetry hub used to send telemetry data.
     telemetry: TelemetryHub,
+    /// The tag sent with each kind of event.
+    tags: TelemetryTags,
+    /// The actuator driven by each fire.
+    actuator: Arc<dyn PropulsionActuator>,
+    /// The clock every deadline is scheduled and checked against.
+    clock: Arc<dyn Clock>,
+    /// Whether fires are only simulated, skipping the actuator.
+    dry_run: bool,
+    /// The thrust curve sampled during each burn.
+    thrust_profile: ThrustProfile,
+    /// Notified whenever the schedule changes so a waiting scheduler can re-arm.
+    wake: Arc<Notify>,
+    /// Whether an abort has latched, rejecting every schedule until cleared.
+    aborted: bool,
+    /// The actuator fault that has latched, rejecting every schedule until
+    /// cleared or the actuator reports healthy.
+    fault: Option<String>,
+    /// When the schedule was paused, if it is currently held.
+    paused_at: Option<Instant>,
+    /// The lifetime command and fire counters.
+    metrics: ProcessorMetrics,
+    /// The most recently handled commands, oldest first.
+    history: VecDeque<HistoryEntry>,
 }
 
 impl CommandProcessor {
-    /// Creates a new `CommandProcessor`.
+    /// Creates a new `CommandProcessor` accepting delays between
+    /// `DEFAULT_MIN_DELAY_SECS` and `DEFAULT_MAX_DELAY_SECS`, scheduling on a
+    /// `TokioClock`, whose fires drive a `NoopActuator`.
     ///
     /// # Arguments
     ///
     /// * `telemetry` - The telemetry hub for sending telemetry data.
     pub fn new(telemetry: TelemetryHub) -> Self {
         Self {
-            scheduled_fire: None,
+            scheduled_fires: Vec::new(),
+            active_burns: Vec::new(),
+            next_id: 1,
+            min_delay: DEFAULT_MIN_DELAY_SECS,
+            max_delay: DEFAULT_MAX_DELAY_SECS,
+            max_horizon: DEFAULT_MAX_HORIZON,
+            stale_tolerance: None,
+            dedupe_window: None,
+            persistence_path: None,
             telemetry,
+            tags: TelemetryTags::default(),
+            actuator: Arc::new(NoopActuator),
+            clock: Arc::new(TokioClock),
+            dry_run: false,
+            thrust_profile: ThrustProfile::default(),
+            wake: Arc::new(Notify::new()),
+            aborted: false,
+            fault: None,
+            paused_at: None,
+            metrics: ProcessorMetrics::default(),
+            history: VecDeque::new(),
+        }
+    }
+
+    /// Sets the range of fire delays the processor accepts.
+    ///
+    /// Schedules outside the range are rejected as invalid.
+    ///
+    /// # Arguments
+    ///
+    /// * `min_secs` - The shortest accepted delay, in seconds.
+    /// * `max_secs` - The longest accepted delay, in seconds.
+    pub fn with_delay_bounds(mut self, min_secs: f64, max_secs: f64) -> Self {
+        self.min_delay = min_secs;
+        self.max_delay = max_secs;
+        self
+    }
+
+    /// Sets the mission window, past which no fire may be scheduled.
+    ///
+    /// Unlike the delay bounds this caps the resulting deadline, so it applies
+    /// to absolute `at_ms` schedules as well as relative ones. Schedules whose
+    /// deadline falls beyond `now + horizon` are rejected as invalid.
+    ///
+    /// # Arguments
+    ///
+    /// * `horizon` - The longest time from now a fire may be scheduled for.
+    pub fn with_max_horizon(mut self, horizon: Duration) -> Self {
+        self.max_horizon = horizon;
+        self
+    }
+
+    /// Sets how overdue a fire may be when it is processed and still ignite.
+    ///
+    /// A fire processed more than the tolerance past its deadline, for example
+    /// because the tick loop was starved, is expired instead of fired, with a
+    /// Warn `⚠️` telemetry message such as `Fire #6 expired, was 2.50s overdue`.
+    ///
+    /// # Arguments
+    ///
+    /// * `tolerance` - The longest a fire may be overdue, or `None`, the
+    ///   default, to fire however late it is.
+    pub fn with_stale_tolerance(mut self, tolerance: Option<Duration>) -> Self {
+        self.stale_tolerance = tolerance;
+        self
+    }
+
+    /// Sets the tag sent with each kind of event.
+    ///
+    /// # Arguments
+    ///
+    /// * `tags` - The tags to use, the emoji set of `TelemetryTags::default` by default.
+    pub fn with_tags(mut self, tags: TelemetryTags) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    /// Enables or disables coalescing duplicate schedules, such as a flaky
+    /// controller sending the same schedule several times in a burst.
+    ///
+    /// A schedule for the same stage, priority and burn duration as a pending
+    /// fire, whose deadline is within the window of that fire's, is accepted
+    /// as that fire instead of arming another, with an Info telemetry message
+    /// noting the coalesce.
+    ///
+    /// # Arguments
+    ///
+    /// * `window` - The largest deadline difference to coalesce, for example
+    ///   `DEFAULT_DEDUPE_WINDOW`, or `None`, the default, to never coalesce.
+    pub fn with_dedupe_window(mut self, window: Option<Duration>) -> Self {
+        self.dedupe_window = window;
+        self
+    }
+
+    /// Sets the actuator driven by each fire, `NoopActuator` by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `actuator` - The actuator whose `fire` is called as each fire ignites.
+    pub fn with_actuator(mut self, actuator: Arc<dyn PropulsionActuator>) -> Self {
+        self.actuator = actuator;
+        self
+    }
+
+    /// Sets the clock every deadline is scheduled and checked against,
+    /// `TokioClock` by default.
+    ///
+    /// A `MockClock` lets tests advance time by hand and so check fire timing,
+    /// countdowns and jitter exactly. Set the clock before scheduling anything,
+    /// as pending deadlines are not carried over to the new clock.
+    ///
+    /// # Arguments
+    ///
+    /// * `clock` - The clock to read the current time from.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Enables or disables dry-run mode.
+    ///
+    /// In dry run, commands, countdowns and burns run as usual, but fires never
+    /// call the actuator, and fire and burn telemetry is prefixed with
+    /// `[DRYRUN]` so a rehearsal cannot be mistaken for a real burn.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether fires should only be simulated.
+    pub fn with_dry_run(mut self, enabled: bool) -> Self {
+        self.dry_run = enabled;
+        self
+    }
+
+    /// Sets the thrust curve sampled during each burn.
+    ///
+    /// While a burn is in progress a `🔥` telemetry message such as
+    /// `thrust=72.5% on 'main'` is sent every `sample_interval`, starting at
+    /// ignition and ending with a sample at the end of the burn.
+    ///
+    /// # Arguments
+    ///
+    /// * `profile` - The thrust curve, a flat 100% by default.
+    pub fn with_thrust_profile(mut self, profile: ThrustProfile) -> Self {
+        self.thrust_profile = profile;
+        self
+    }
+
+    /// Enables or disables persisting the pending schedule to a JSON file.
+    ///
+    /// The schedule is saved, as absolute wall-clock times, whenever it changes.
+    /// If `path` already holds a saved schedule, every fire still in the future
+    /// is re-armed; fires whose time passed while the processor was down are
+    /// dropped with a telemetry note.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The file to save the schedule to, or `None` to keep it in memory only.
+    pub async fn with_persistence(mut self, path: Option<PathBuf>) -> Self {
+        self.persistence_path = path;
+        match self.load_schedule().await {
+            Ok(Some(schedule)) => self.restore_schedule(schedule).await,
+            Ok(None) => {}
+            Err(e) => {
+                let msg = format!("Failed to load persisted schedule: {}", e);
+                self.telemetry
+                    .send_telemetry_with_severity(Severity::Warn, &self.tags.warning, &msg)
+                    .await;
+            }
+        }
+        self
+    }
+
+    /// Returns the earliest pending ignition, countdown mark, thrust sample, or
+    /// burn completion time, if any. While paused only burns are due.
+    pub fn next_deadline(&self) -> Option<Instant> {
+        let held = self.paused_at.is_some();
+        let next_fire = self
+            .scheduled_fires
+            .first()
+            .map(|fire| fire.deadline)
+            .filter(|_| !held);
+        let next_countdown = self
+            .scheduled_fires
+            .iter()
+            .filter_map(ScheduledFire::next_countdown)
+            .min()
+            .filter(|_| !held);
+        let next_sample = self
+            .active_burns
+            .iter()
+            .filter_map(|burn| burn.next_sample)
+            .min();
+        let next_burn_end = self.active_burns.first().map(|burn| burn.end);
+        [next_fire, next_countdown, next_sample, next_burn_end]
+            .into_iter()
+            .flatten()
+            .min()
+    }
+
+    /// Returns how long until the next pending fire ignites, for host code
+    /// deciding whether it can enter a low-power wait.
+    ///
+    /// While paused, the time is frozen at what remained when the pause began.
+    ///
+    /// # Returns
+    ///
+    /// The time remaining until the earliest fire, `Some(Duration::ZERO)` if
+    /// it is overdue but not yet processed by `tick`, or `None` when no fire is
+    /// pending.
+    pub fn time_until_next_fire(&self) -> Option<Duration> {
+        let now = self.paused_at.unwrap_or_else(|| self.clock.now());
+        self.scheduled_fires
+            .first()
+            .map(|fire| fire.deadline.saturating_duration_since(now))
+    }
+
+    /// Reconstructs a processor from a snapshot, re-arming each fire and burn
+    /// with its remaining time measured from now on a `TokioClock`.
+    ///
+    /// Every other setting starts at its default, as for `new`. Negative
+    /// times are taken as already elapsed.
+    ///
+    /// # Arguments
+    ///
+    /// * `snapshot` - A snapshot taken with `snapshot`.
+    /// * `telemetry` - The telemetry hub for sending telemetry data.
+    ///
+    /// # Returns
+    ///
+    /// The processor, or a `FlightComputerError::SchedulingError` if a time in
+    /// the snapshot is not finite or too large to schedule.
+    pub fn from_snapshot(
+        snapshot: ProcessorSnapshot,
+        telemetry: TelemetryHub,
+    ) -> Result<Self, FlightComputerError> {
+        let mut processor = Self::new(telemetry);
+        let now = processor.clock.now();
+        processor.next_id = snapshot.next_id;
+        processor.aborted = snapshot.aborted;
+        processor.paused_at = snapshot.paused.then_some(now);
+        processor.metrics = snapshot.metrics;
+        let invalid = |what: &str, id: u64, secs: f64| {
+            FlightComputerError::SchedulingError(format!(
+                "snapshot {} #{} has an invalid time of {} seconds",
+                what, id, secs
+            ))
+        };
+        for fire in snapshot.fires {
+            let deadline = checked_duration(fire.remaining_secs)
+                .and_then(|remaining| now.checked_add(remaining))
+                .ok_or_else(|| invalid("fire", fire.id, fire.remaining_secs))?;
+            let burn = checked_duration(fire.burn_secs)
+                .filter(|&burn| deadline.checked_add(burn).is_some())
+                .ok_or_else(|| invalid("fire", fire.id, fire.burn_secs))?;
+            processor.insert_fire(ScheduledFire {
+                id: fire.id,
+                stage: fire.stage,
+                priority: fire.priority,
+                deadline,
+                burn,
+                countdown: countdown_start(fire.remaining_secs),
+            });
+        }
+        for burn in snapshot.burns {
+            let elapsed = checked_duration(burn.elapsed_secs)
+                .ok_or_else(|| invalid("burn", burn.id, burn.elapsed_secs))?;
+            let end = checked_duration(burn.remaining_secs)
+                .and_then(|remaining| now.checked_add(remaining))
+                .ok_or_else(|| invalid("burn", burn.id, burn.remaining_secs))?;
+            processor.active_burns.push(ActiveBurn {
+                id: burn.id,
+                stage: burn.stage,
+                start: now.checked_sub(elapsed).unwrap_or(now),
+                end,
+                next_sample: Some(now),
+            });
         }
+        processor.active_burns.sort_by_key(|burn| burn.end);
+        Ok(processor)
+    }
+
+    /// Captures the processor's live state.
+    ///
+    /// Deadlines are stored as the time remaining from now, or from the pause
+    /// while paused, so they can be re-armed by `from_snapshot` against a new clock.
+    pub fn snapshot(&self) -> ProcessorSnapshot {
+        let now = self.clock.now();
+        let held_at = self.paused_at.unwrap_or(now);
+        ProcessorSnapshot {
+            next_id: self.next_id,
+            fires: self
+                .scheduled_fires
+                .iter()
+                .map(|fire| FireSnapshot {
+                    id: fire.id,
+                    stage: fire.stage.clone(),
+                    remaining_secs: fire
+                        .deadline
+                        .saturating_duration_since(held_at)
+                        .as_secs_f64(),
+                    burn_secs: fire.burn.as_secs_f64(),
+                    priority: fire.priority,
+                })
+                .collect(),
+            burns: self
+                .active_burns
+                .iter()
+                .map(|burn| BurnSnapshot {
+                    id: burn.id,
+                    stage: burn.stage.clone(),
+                    elapsed_secs: now.saturating_duration_since(burn.start).as_secs_f64(),
+                    remaining_secs: burn.end.saturating_duration_since(now).as_secs_f64(),
+                })
+                .collect(),
+            aborted: self.aborted,
+            paused: self.paused_at.is_some(),
+            metrics: self.metrics,
+        }
+    }
+
+    /// Returns the shortest and longest accepted fire delays, in seconds.
+    pub fn delay_bounds(&self) -> (f64, f64) {
+        (self.min_delay, self.max_delay)
+    }
+
+    /// Returns a snapshot of the processor's lifetime counters.
+    pub fn metrics(&self) -> ProcessorMetrics {
+        self.metrics
+    }
+
+    /// Returns the most recently handled commands and their outcomes, oldest first.
+    ///
+    /// Only the last `HISTORY_CAPACITY` commands are kept.
+    pub fn history(&self) -> Vec<HistoryEntry> {
+        self.history.iter().cloned().collect()
+    }
+
+    /// Returns a handle that is notified whenever the schedule changes.
+    ///
+    /// A scheduler sleeping until `next_deadline` should wait on this as well
+    /// and re-read the deadline when it fires.
+    pub fn schedule_changed(&self) -> Arc<Notify> {
+        self.wake.clone()
     }
 
     /// Handles an incoming command.
     ///
-    /// Depending on the delay value, it either cancels, shedules, or marks the command as invalid.
+    /// A schedule with a non-negative delay arms a new fire, a cancel drops the
+    /// fire with the given id or every fire, an abort stops everything and
+    /// rejects further schedules until a clear, and anything else marks the
+    /// command as invalid. Every command is recorded in the history along with
+    /// its outcome.
     ///
     /// # Arguments
     ///
-    /// * `Command(delay)` - The command containing the delay value.
-    pub async fn handle(&mut self, Command(delay): Command) {
-        if delay == -1.0 {
-            self.cancel().await;
-        } else if delay >= 0.0 {
-            self.schedule(delay).await;
-        } else {
-            self.invalid(delay).await;
+    /// * `command` - The command to handle.
+    ///
+    /// # Returns
+    ///
+    /// The `CommandOutcome` to acknowledge back to the sender.
+    pub async fn handle(&mut self, command: Command) -> CommandOutcome {
+        self.metrics.commands_received += 1;
+        let at = SystemTime::now();
+        let outcome = match command.clone() {
+            Command::Schedule {
+                when: FireTime::Delay(delay),
+                unit,
+                stage,
+                burn,
+                priority,
+            } => {
+                self.schedule(stage, unit.to_secs(delay), burn, priority)
+                    .await
+            }
+            Command::Schedule {
+                when: FireTime::AtMs(at_ms),
+                stage,
+                burn,
+                priority,
+                ..
+            } => self.schedule_at(stage, at_ms, burn, priority).await,
+            Command::Cancel { id: None, stage } => self.cancel(stage.as_deref()).await,
+            Command::Cancel { id: Some(id), .. } => self.cancel_id(id).await,
+            Command::Abort => self.abort().await,
+            Command::Clear => self.clear().await,
+            Command::ClearFault => self.clear_fault().await,
+            Command::Pause => self.pause().await,
+            Command::Resume => self.resume().await,
+            Command::Status => self.status().await,
+            Command::Reset { clear_metrics } => self.reset(clear_metrics).await,
+        };
+        if self.history.len() == HISTORY_CAPACITY {
+            self.history.pop_front();
         }
+        self.history.push_back(HistoryEntry {
+            at,
+            command,
+            outcome: outcome.clone(),
+        });
+        outcome
+    }
+
+    /// Cancels every scheduled propulsion event and aborts any burn in progress,
+    /// sending a single critical telemetry message.
+    ///
+    /// The processor stays aborted, rejecting every schedule, until `clear` is called.
+    async fn abort(&mut self) -> CommandOutcome {
+        self.aborted = true;
+        self.scheduled_fires.clear();
+        self.active_burns.clear();
+        self.wake.notify_one();
+        self.persist().await;
+        self.telemetry
+            .send_telemetry_with_severity(Severity::Critical, &self.tags.abort, "ABORT")
+            .await;
+        CommandOutcome::accepted()
     }
 
-    /// Cancels any scheduled propulsion event.
-    async fn cancel(&mut self) {
-        self.scheduled_fire = None;
+    /// Releases the abort latch so fires can be scheduled again.
+    async fn clear(&mut self) -> CommandOutcome {
+        self.aborted = false;
         self.telemetry
-            .send_telemetry("🛑", "Cancelled fire command")
+            .send_telemetry(&self.tags.cleared, "Abort cleared, scheduling enabled")
             .await;
+        CommandOutcome::accepted()
     }
 
-    /// Schedules a propulsion event after the specified delay.
+    /// Returns the processor to idle, as if newly created with the same
+    /// configuration, sending a single telemetry message.
+    ///
+    /// Every pending fire and burn in progress is dropped without firing, and
+    /// the pause, abort and fault latches are released. Fire ids keep counting
+    /// up, so an id never refers to two different fires, and the history is kept.
     ///
     /// # Arguments
     ///
-    /// * `secs` - The delay in seconds before firing.
-    async fn schedule(&mut self, secs: f64) {
-        let when = Instant::now() + Duration::from_secs_f64(secs);
-        self.scheduled_fire = Some(when);
-        let msg = format!("Scheduled fire in {:.2}s", secs);
-        self.telemetry.send_telemetry("🛰️ ⏳", &msg).await;
+    /// * `clear_metrics` - Whether the metrics counters are zeroed too.
+    async fn reset(&mut self, clear_metrics: bool) -> CommandOutcome {
+        self.scheduled_fires.clear();
+        self.active_burns.clear();
+        self.aborted = false;
+        self.fault = None;
+        self.paused_at = None;
+        if clear_metrics {
+            self.metrics = ProcessorMetrics::default();
+        }
+        self.wake.notify_one();
+        self.persist().await;
+        self.telemetry
+            .send_telemetry(&self.tags.reset, "System reset to idle")
+            .await;
+        CommandOutcome::accepted()
     }
 
-    /// Marks a command as invalid and sends a telemetry message.
-    ///
-    /// # Arguments
+    /// Releases a latched actuator fault so fires can be scheduled again.
     ///
-    /// * `value` - The invalid delay value.
-    async fn invalid(&self, value: f64) {
-        let msg = format!("Invalid delay value: {}", value);
-        self.telemetry.send_telemetry("⚠️", &msg).await;
+    /// If the actuator still reports a fault, it latches again at the next check.
+    async fn clear_fault(&mut self) -> CommandOutcome {
+        if self.fault.take().is_none() {
+            return self
+                .invalid("No actuator fault is latched".to_string())
+                .await;
+        }
+        self.telemetry
+            .send_telemetry(
+                &self.tags.cleared,
+                "Actuator fault cleared, scheduling enabled",
+            )
+            .await;
+        CommandOutcome::accepted()
     }
 
-    /// Checks if it's time to fire propulsion and sends a telemetry message if so.
-    pub async fn tick(&mut self) {
-        if let Some(when) = self.scheduled_fire {
-            if Instant::now() >= when {
-                self.scheduled_fire = None;
+    /// Polls the actuator's fault status, latching a newly reported fault with
+    /// a Critical telemetry message and releasing the latch once the actuator
+    /// reports healthy.
+    async fn check_fault(&mut self) {
+        match (self.actuator.fault(), &self.fault) {
+            (Some(fault), None) => {
+                let msg = format!("Actuator fault latched: {}", fault);
+                self.fault = Some(fault);
+                self.telemetry
+                    .send_telemetry_with_severity(Severity::Critical, &self.tags.warning, &msg)
+                    .await;
+            }
+            (None, Some(_)) => {
+                self.fault = None;
                 self.telemetry
-                    .send_telemetry("🚀", "Firing propulsion now!")
+                    .send_telemetry(
+                        &self.tags.cleared,
+                        "Actuator reports healthy, fault cleared",
+                    )
                     .await;
             }
+            _ => {}
+        }
+    }
+
+    /// Holds every pending fire, and any scheduled while paused, until `resume`.
+    ///
+    /// Burns already in progress run to completion.
+    async fn pause(&mut self) -> CommandOutcome {
+        if self.paused_at.is_some() {
+            return self
+                .invalid("Scheduling is already paused".to_string())
+                .await;
+        }
+        self.paused_at = Some(self.clock.now());
+        self.wake.notify_one();
+        let msg = format!(
+            "Scheduling paused, holding {} pending fires",
+            self.scheduled_fires.len()
+        );
+        self.telemetry.send_telemetry(&self.tags.pause, &msg).await;
+        CommandOutcome::accepted()
+    }
+
+    /// Re-arms every held fire with the time it had remaining when paused, or
+    /// its full delay if it was scheduled while paused.
+    ///
+    /// Held deadlines are measured from the pause, so shifting them all by
+    /// the length of the pause re-arms each at now plus its remaining time. A
+    /// fire that was already overdue when paused is due immediately rather
+    /// than being dropped.
+    async fn resume(&mut self) -> CommandOutcome {
+        let Some(paused_at) = self.paused_at.take() else {
+            return self.invalid("Scheduling is not paused".to_string()).await;
+        };
+        let now = self.clock.now();
+        let held = now.saturating_duration_since(paused_at);
+        for fire in &mut self.scheduled_fires {
+            // A deadline too far out to shift is as good as never anyway.
+            if let Some(deadline) = fire.deadline.checked_add(held) {
+                fire.deadline = deadline;
+            }
+            let remaining = fire.deadline.saturating_duration_since(now);
+            fire.countdown = countdown_start(remaining.as_secs_f64());
+        }
+        self.wake.notify_one();
+        self.persist().await;
+        let msg = format!(
+            "Scheduling resumed after {:.1}s, re-armed {} pending fires",
+            held.as_secs_f64(),
+            self.scheduled_fires.len()
+        );
+        self.telemetry.send_telemetry(&self.tags.resume, &msg).await;
+        CommandOutcome::accepted()
+    }
+
+    /// Sends a telemetry message summarizing the pending fires and active burns,
+    /// e.g. `Status: 2 pending [#6 'main' in 1.3s, #7 'aux' in 4.0s]`, or `Status: idle`,
+    /// followed by the lifetime counters. While paused, pending times are as of the pause.
+    async fn status(&self) -> CommandOutcome {
+        let metrics = self.metrics;
+        let msg = format!(
+            "{} (commands={}, scheduled={}, cancelled={}, invalid={}, fired={})",
+            self.status_summary(self.clock.now()),
+            metrics.commands_received,
+            metrics.schedules_accepted,
+            metrics.cancels,
+            metrics.invalid_commands,
+            metrics.fires_executed
+        );
+        self.telemetry.send_telemetry(&self.tags.status, &msg).await;
+        CommandOutcome::accepted()
+    }
+
+    /// Builds the status summary with remaining times measured from `now`.
+    fn status_summary(&self, now: Instant) -> String {
+        if self.scheduled_fires.is_empty() && self.active_burns.is_empty() {
+            return "Status: idle".to_string();
+        }
+
+        let held_at = self.paused_at.unwrap_or(now);
+        let pending: Vec<String> = self
+            .scheduled_fires
+            .iter()
+            .map(|fire| {
+                let remaining = fire.deadline.saturating_duration_since(held_at);
+                format!(
+                    "#{} '{}' in {:.1}s",
+                    fire.id,
+                    fire.stage,
+                    remaining.as_secs_f64()
+                )
+            })
+            .collect();
+        let mut msg = format!("Status: {} pending [{}]", pending.len(), pending.join(", "));
+        if self.paused_at.is_some() {
+            msg.push_str(" (paused)");
+        }
+
+        if !self.active_burns.is_empty() {
+            let burning: Vec<String> = self
+                .active_burns
+                .iter()
+                .map(|burn| {
+                    let remaining = burn.end.saturating_duration_since(now);
+                    format!(
+                        "#{} '{}' for {:.1}s",
+                        burn.id,
+                        burn.stage,
+                        remaining.as_secs_f64()
+                    )
+                })
+                .collect();
+            msg.push_str(&format!(", burning [{}]", burning.join(", ")));
+        }
+        msg
+    }
+
+    /// Cancels every scheduled propulsion event and aborts any burn in progress
+    /// on the given stage, or on every stage.
+    ///
+    /// # Arguments
+    ///
+    /// * `stage` - The stage to cancel, or `None` for every stage.
+    async fn cancel(&mut self, stage: Option<&str>) -> CommandOutcome {
+        let on_stage = |other: &str| stage.is_none_or(|stage| stage == other);
+        self.metrics.cancels += 1;
+        self.scheduled_fires.retain(|fire| !on_stage(&fire.stage));
+        self.wake.notify_one();
+        self.persist().await;
+        let msg = match stage {
+            Some(stage) => format!("Cancelled '{}' fire commands", stage),
+            None => "Cancelled fire command".to_string(),
+        };
+        self.telemetry.send_telemetry(&self.tags.cancel, &msg).await;
+
+        let burning = self.active_burns.len();
+        self.active_burns.retain(|burn| !on_stage(&burn.stage));
+        if self.active_burns.len() < burning {
+            let msg = match stage {
+                Some(stage) => format!("Burn '{}' aborted", stage),
+                None => "Burn aborted".to_string(),
+            };
+            self.telemetry
+                .send_telemetry_with_severity(Severity::Critical, &self.tags.cancel, &msg)
+                .await;
+        }
+        CommandOutcome::accepted()
+    }
+
+    /// Cancels the scheduled propulsion event, or aborts the burn in progress,
+    /// with the given id.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The id reported when the fire was scheduled.
+    async fn cancel_id(&mut self, id: u64) -> CommandOutcome {
+        if let Some(index) = self.scheduled_fires.iter().position(|fire| fire.id == id) {
+            self.scheduled_fires.remove(index);
+            self.wake.notify_one();
+            self.persist().await;
+            let msg = format!("Cancelled fire #{}", id);
+            self.telemetry.send_telemetry(&self.tags.cancel, &msg).await;
+        } else if let Some(index) = self.active_burns.iter().position(|burn| burn.id == id) {
+            self.active_burns.remove(index);
+            self.wake.notify_one();
+            let msg = format!("Burn #{} aborted", id);
+            self.telemetry
+                .send_telemetry_with_severity(Severity::Critical, &self.tags.cancel, &msg)
+                .await;
+        } else {
+            return self
+                .invalid(format!("No pending fire #{} to cancel", id))
+                .await;
+        }
+        self.metrics.cancels += 1;
+        CommandOutcome::Accepted { id: Some(id) }
+    }
+
+    /// Schedules a propulsion event on a stage after the specified delay.
+    ///
+    /// Previously scheduled events, on this stage or others, stay pending
+    /// alongside the new one.
+    ///
+    /// # Arguments
+    ///
+    /// * `stage` - The propulsion stage to fire.
+    /// * `secs` - The delay in seconds before firing.
+    /// * `burn_secs` - How long the propulsion burns, `0.0` for an instantaneous fire.
+    /// * `priority` - Orders the fire among fires with the same deadline, highest first.
+    ///
+    /// # Returns
+    ///
+    /// An accepted outcome carrying the id assigned to the fire, or a rejection.
+    async fn schedule(
+        &mut self,
+        stage: String,
+        secs: f64,
+        burn_secs: f64,
+        priority: u8,
+    ) -> CommandOutcome {
+        if self.aborted {
+            return self
+                .invalid("Aborted, scheduling is locked out until cleared".to_string())
+                .await;
         }
+        self.check_fault().await;
+        if let Some(fault) = &self.fault {
+            let reason = format!(
+                "Actuator fault latched ({}), scheduling is locked out until cleared",
+                fault
+            );
+            self.metrics.invalid_commands += 1;
+            self.telemetry
+                .send_telemetry_with_severity(Severity::Critical, &self.tags.warning, &reason)
+                .await;
+            return CommandOutcome::Rejected { reason };
+        }
+        if !secs.is_finite() {
+            return self
+                .invalid(format!("Invalid non-finite delay value: {}", secs))
+                .await;
+        }
+        if !burn_secs.is_finite() {
+            return self
+                .invalid(format!("Invalid non-finite burn duration: {}", burn_secs))
+                .await;
+        }
+        if secs < 0.0 {
+            return self.invalid(format!("Invalid delay value: {}", secs)).await;
+        }
+        if secs < self.min_delay {
+            return self
+                .invalid(format!(
+                    "Delay {}s is below the minimum of {}s",
+                    secs, self.min_delay
+                ))
+                .await;
+        }
+        if secs > self.max_delay {
+            return self
+                .invalid(format!(
+                    "Delay {}s exceeds the maximum of {}s",
+                    secs, self.max_delay
+                ))
+                .await;
+        }
+        if secs > self.max_horizon.as_secs_f64() {
+            return self
+                .invalid(format!(
+                    "Fire in {}s is beyond the scheduling horizon of {}s",
+                    secs,
+                    self.max_horizon.as_secs_f64()
+                ))
+                .await;
+        }
+        if burn_secs < 0.0 {
+            return self
+                .invalid(format!("Invalid burn duration: {}", burn_secs))
+                .await;
+        }
+        if stage.is_empty() {
+            return self.invalid("Invalid empty stage name".to_string()).await;
+        }
+
+        // While paused, time is held at the pause, so the fire keeps its full
+        // delay until `resume` re-arms it.
+        let scheduled_at = self.paused_at.unwrap_or_else(|| self.clock.now());
+        // Finite values within the bounds can still be too large for a
+        // `Duration`, or put the deadline or the end of the burn past the
+        // latest representable `Instant`, and adding them would panic.
+        let Some(deadline) = Duration::try_from_secs_f64(secs)
+            .ok()
+            .and_then(|delay| scheduled_at.checked_add(delay))
+        else {
+            return self
+                .invalid(format!(
+                    "Delay {}s is too far in the future to schedule",
+                    secs
+                ))
+                .await;
+        };
+        let Some(burn) = Duration::try_from_secs_f64(burn_secs)
+            .ok()
+            .filter(|&burn| deadline.checked_add(burn).is_some())
+        else {
+            return self
+                .invalid(format!(
+                    "Burn duration {}s is too long to schedule",
+                    burn_secs
+                ))
+                .await;
+        };
+        if let Some(window) = self.dedupe_window
+            && let Some(duplicate) = self.scheduled_fires.iter().find(|fire| {
+                fire.stage == stage
+                    && fire.priority == priority
+                    && fire.burn == burn
+                    && fire.deadline.max(deadline) - fire.deadline.min(deadline) <= window
+            })
+        {
+            let id = duplicate.id;
+            let msg = format!("Coalesced duplicate '{}' schedule into fire #{}", stage, id);
+            self.telemetry
+                .send_telemetry(&self.tags.schedule, &msg)
+                .await;
+            return CommandOutcome::Accepted { id: Some(id) };
+        }
+
+        let id = self.next_id;
+        self.next_id += 1;
+        let msg = if burn_secs == 0.0 {
+            format!("Scheduled '{}' fire #{} in {:.2}s", stage, id, secs)
+        } else {
+            format!(
+                "Scheduled '{}' fire #{} in {:.2}s, burning for {:.2}s",
+                stage, id, secs, burn_secs
+            )
+        };
+        self.insert_fire(ScheduledFire {
+            id,
+            stage,
+            priority,
+            deadline,
+            burn,
+            countdown: countdown_start(secs),
+        });
+        self.wake.notify_one();
+        self.persist().await;
+        self.metrics.schedules_accepted += 1;
+
+        self.telemetry
+            .send_telemetry(&self.tags.schedule, &msg)
+            .await;
+        CommandOutcome::Accepted { id: Some(id) }
+    }
+
+    /// Schedules a propulsion event on a stage at an absolute wall-clock time.
+    ///
+    /// The time is converted to a delay from now, which is then validated like
+    /// any other delay.
+    ///
+    /// # Arguments
+    ///
+    /// * `stage` - The propulsion stage to fire.
+    /// * `at_ms` - The ignition time, in milliseconds since the Unix epoch.
+    /// * `burn_secs` - How long the propulsion burns, `0.0` for an instantaneous fire.
+    /// * `priority` - Orders the fire among fires with the same deadline, highest first.
+    ///
+    /// # Returns
+    ///
+    /// An accepted outcome carrying the id assigned to the fire, or a rejection
+    /// if the time has already passed.
+    async fn schedule_at(
+        &mut self,
+        stage: String,
+        at_ms: u64,
+        burn_secs: f64,
+        priority: u8,
+    ) -> CommandOutcome {
+        let fire_at = UNIX_EPOCH + Duration::from_millis(at_ms);
+        match fire_at.duration_since(SystemTime::now()) {
+            Ok(remaining) => {
+                self.schedule(stage, remaining.as_secs_f64(), burn_secs, priority)
+                    .await
+            }
+            Err(_) => {
+                self.invalid(format!("Fire time {}ms is in the past", at_ms))
+                    .await
+            }
+        }
+    }
+
+    /// Inserts a fire into the pending queue, keeping it ordered by deadline,
+    /// then by priority from highest, then by insertion.
+    fn insert_fire(&mut self, fire: ScheduledFire) {
+        let index = self.scheduled_fires.partition_point(|pending| {
+            (pending.deadline, Reverse(pending.priority)) <= (fire.deadline, Reverse(fire.priority))
+        });
+        self.scheduled_fires.insert(index, fire);
+    }
+
+    /// Reads the persisted schedule, if persistence is enabled and one was saved.
+    async fn load_schedule(&self) -> Result<Option<PersistedSchedule>, FlightComputerError> {
+        let Some(path) = &self.persistence_path else {
+            return Ok(None);
+        };
+        match fs::read(path).await {
+            Ok(contents) => Ok(Some(serde_json::from_slice(&contents)?)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Re-arms every persisted fire that is still in the future.
+    async fn restore_schedule(&mut self, schedule: PersistedSchedule) {
+        let now = self.clock.now();
+        let wall_now = SystemTime::now();
+        self.next_id = self.next_id.max(schedule.next_id);
+
+        for persisted in schedule.fires {
+            let fire_at = UNIX_EPOCH + Duration::from_millis(persisted.fire_at_ms);
+            let Ok(remaining) = fire_at.duration_since(wall_now) else {
+                let msg = format!("Dropped fire #{} that was due while offline", persisted.id);
+                self.telemetry
+                    .send_telemetry_with_severity(Severity::Warn, &self.tags.warning, &msg)
+                    .await;
+                continue;
+            };
+            let armed = now.checked_add(remaining).and_then(|deadline| {
+                let burn = checked_duration(persisted.burn_secs)?;
+                deadline.checked_add(burn).map(|_| (deadline, burn))
+            });
+            let Some((deadline, burn)) = armed else {
+                let msg = format!(
+                    "Dropped fire #{} with an invalid time or burn duration",
+                    persisted.id
+                );
+                self.telemetry
+                    .send_telemetry_with_severity(Severity::Warn, &self.tags.warning, &msg)
+                    .await;
+                continue;
+            };
+            self.insert_fire(ScheduledFire {
+                id: persisted.id,
+                stage: persisted.stage,
+                priority: persisted.priority,
+                deadline,
+                burn,
+                countdown: countdown_start(remaining.as_secs_f64()),
+            });
+            let msg = format!(
+                "Restored fire #{} in {:.2}s",
+                persisted.id,
+                remaining.as_secs_f64()
+            );
+            self.telemetry
+                .send_telemetry(&self.tags.schedule, &msg)
+                .await;
+        }
+
+        self.wake.notify_one();
+        self.persist().await;
+    }
+
+    /// Saves the pending schedule if persistence is enabled, reporting a
+    /// failure as a warning on telemetry.
+    async fn persist(&self) {
+        let Some(path) = &self.persistence_path else {
+            return;
+        };
+        if let Err(e) = self.save_schedule(path).await {
+            let msg = format!("Failed to persist schedule to {}: {}", path.display(), e);
+            self.telemetry
+                .send_telemetry_with_severity(Severity::Warn, &self.tags.warning, &msg)
+                .await;
+        }
+    }
+
+    /// Writes the pending schedule, as absolute wall-clock times, to `path`.
+    /// While paused, fire times are saved as of the pause.
+    async fn save_schedule(&self, path: &Path) -> io::Result<()> {
+        let now = self.paused_at.unwrap_or_else(|| self.clock.now());
+        let wall_now = SystemTime::now();
+        let schedule = PersistedSchedule {
+            next_id: self.next_id,
+            fires: self
+                .scheduled_fires
+                .iter()
+                .map(|fire| {
+                    // A fire too far out for the wall clock is saved as never.
+                    let fire_at_ms = wall_now
+                        .checked_add(fire.deadline.saturating_duration_since(now))
+                        .map_or(u64::MAX, |fire_at| {
+                            let since_epoch =
+                                fire_at.duration_since(UNIX_EPOCH).unwrap_or_default();
+                            u64::try_from(since_epoch.as_millis()).unwrap_or(u64::MAX)
+                        });
+                    PersistedFire {
+                        id: fire.id,
+                        stage: fire.stage.clone(),
+                        fire_at_ms,
+                        burn_secs: fire.burn.as_secs_f64(),
+                        priority: fire.priority,
+                    }
+                })
+                .collect(),
+        };
+        fs::write(path, serde_json::to_vec(&schedule)?).await
+    }
+
+    /// Marks a command as invalid and sends a telemetry message.
+    ///
+    /// This is also used for commands that could not be parsed, so they are
+    /// counted and reported like any other invalid command.
+    ///
+    /// # Arguments
+    ///
+    /// * `reason` - A description of why the command was rejected.
+    ///
+    /// # Returns
+    ///
+    /// A rejected outcome carrying `reason`.
+    pub async fn invalid(&mut self, reason: String) -> CommandOutcome {
+        self.metrics.invalid_commands += 1;
+        self.telemetry
+            .send_telemetry_with_severity(Severity::Warn, &self.tags.warning, &reason)
+            .await;
+        CommandOutcome::Rejected { reason }
+    }
+
+    /// Returns the prefix marking simulated fire telemetry, empty unless in dry run.
+    fn dry_run_marker(&self) -> &'static str {
+        if self.dry_run { "[DRYRUN] " } else { "" }
+    }
+
+    /// Fires every scheduled propulsion event whose deadline has passed, in order,
+    /// announces countdown marks that have been reached, samples the thrust of
+    /// each burn in progress, and completes every burn whose duration has
+    /// elapsed, sending a telemetry message for each. Fire telemetry reports
+    /// how late ignition was against the scheduled deadline, and fires later
+    /// than the stale tolerance are expired rather than fired.
+    ///
+    /// This is called by the scheduler once `next_deadline` has been reached,
+    /// but is safe to call at any time. While paused, nothing fires and no
+    /// countdown marks are announced. Each tick also checks the actuator for
+    /// a fault.
+    pub async fn tick(&mut self) {
+        self.check_fault().await;
+        let now = self.clock.now();
+        let held = self.paused_at.is_some();
+        let due = if held {
+            0
+        } else {
+            self.scheduled_fires
+                .partition_point(|fire| fire.deadline <= now)
+        };
+        let fired: Vec<ScheduledFire> = self.scheduled_fires.drain(..due).collect();
+        if !fired.is_empty() {
+            self.persist().await;
+        }
+        for fire in fired {
+            // How far ignition trails the deadline, from tick granularity and
+            // any fires handled before this one.
+            let jitter = self.clock.now().saturating_duration_since(fire.deadline);
+            if self
+                .stale_tolerance
+                .is_some_and(|tolerance| jitter > tolerance)
+            {
+                let msg = format!(
+                    "Fire #{} expired, was {:.2}s overdue",
+                    fire.id,
+                    jitter.as_secs_f64()
+                );
+                self.telemetry
+                    .send_telemetry_with_severity(Severity::Warn, &self.tags.warning, &msg)
+                    .await;
+                warn!("fire expired"; id = fire.id, stage = fire.stage, late_ms = jitter.as_millis());
+                continue;
+            }
+            // A deadline shifted by `resume` can leave no room for the burn
+            // before the latest representable `Instant`.
+            let Some(burn_end) = now.checked_add(fire.burn) else {
+                let msg = format!(
+                    "Dropped fire #{} with an invalid time or burn duration",
+                    fire.id
+                );
+                self.telemetry
+                    .send_telemetry_with_severity(Severity::Warn, &self.tags.warning, &msg)
+                    .await;
+                continue;
+            };
+            self.metrics.fires_executed += 1;
+            info!("propulsion fired"; id = fire.id, stage = fire.stage, late_ms = jitter.as_millis(), dry_run = self.dry_run);
+            if !self.dry_run {
+                self.actuator.fire(&fire.stage);
+            }
+            let msg = format!(
+                "{}Firing propulsion '{}' now! (priority {}, +{}ms late)",
+                self.dry_run_marker(),
+                fire.stage,
+                fire.priority,
+                jitter.as_millis()
+            );
+            self.telemetry
+                .send_telemetry_with_severity(Severity::Critical, &self.tags.fire, &msg)
+                .await;
+            if !fire.burn.is_zero() {
+                let burn = ActiveBurn {
+                    id: fire.id,
+                    stage: fire.stage,
+                    start: now,
+                    end: burn_end,
+                    next_sample: Some(now),
+                };
+                let index = self
+                    .active_burns
+                    .partition_point(|other| other.end <= burn.end);
+                self.active_burns.insert(index, burn);
+            }
+        }
+
+        let mut marks = Vec::new();
+        for fire in self.scheduled_fires.iter_mut().filter(|_| !held) {
+            let mut reached = None;
+            while fire.next_countdown().is_some_and(|mark| mark <= now) {
+                reached = Some(fire.countdown);
+                fire.countdown -= 1;
+            }
+            if let Some(secs) = reached {
+                marks.push(format!("T-{}s to fire #{}", secs, fire.id));
+            }
+        }
+        for msg in marks {
+            self.telemetry
+                .send_telemetry(&self.tags.countdown, &msg)
+                .await;
+        }
+
+        let profile = self.thrust_profile;
+        let interval = profile.sample_interval.max(Duration::from_millis(1));
+        let mut samples = Vec::new();
+        for burn in &mut self.active_burns {
+            let mut reached = None;
+            while let Some(at) = burn.next_sample.filter(|at| *at <= now) {
+                reached = Some(at);
+                // Past the latest `Instant` is past the end of the burn too.
+                burn.next_sample = (at < burn.end).then(|| {
+                    at.checked_add(interval)
+                        .map_or(burn.end, |next| next.min(burn.end))
+                });
+            }
+            if let Some(at) = reached {
+                let thrust = profile.thrust_at(at - burn.start, burn.end - burn.start);
+                samples.push(format!("thrust={:.1}% on '{}'", thrust, burn.stage));
+            }
+        }
+        for msg in samples {
+            let msg = format!("{}{}", self.dry_run_marker(), msg);
+            self.telemetry.send_telemetry(&self.tags.thrust, &msg).await;
+        }
+
+        let complete = self.active_burns.partition_point(|burn| burn.end <= now);
+        let completed: Vec<ActiveBurn> = self.active_burns.drain(..complete).collect();
+        for burn in completed {
+            let msg = format!("{}Burn '{}' complete", self.dry_run_marker(), burn.stage);
+            self.telemetry
+                .send_telemetry(&self.tags.burn_complete, &msg)
+                .await;
+        }
+    }
+}
+
+/// Converts seconds read back from outside the processor to a `Duration`,
+/// taking negative values as zero.
+///
+/// # Returns
+///
+/// The duration, or `None` if `secs` is not finite or too large for a `Duration`.
+fn checked_duration(secs: f64) -> Option<Duration> {
+    if !secs.is_finite() {
+        return None;
+    }
+    Duration::try_from_secs_f64(secs.max(0.0)).ok()
+}
+
+/// Returns the first countdown mark for a fire `secs` seconds out: the largest
+/// whole number of seconds below `secs`, capped at `COUNTDOWN_FROM_SECS`, or
+/// `0` when the fire is too close for a countdown.
+fn countdown_start(secs: f64) -> u64 {
+    if secs < COUNTDOWN_MIN_DELAY_SECS {
+        return 0;
+    }
+    (secs.ceil() as u64 - 1).min(COUNTDOWN_FROM_SECS)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+    use crate::telemetry::tests::{Received, connect, next_line, wait_for};
+    use crate::telemetry::{TelemetryFormat, TelemetryMessage};
+    use tokio::time;
+
+    /// Creates a hub with a client receiving its telemetry as bare
+    /// `[tag] payload` lines.
+    async fn telemetry() -> (TelemetryHub, Received) {
+        let hub = TelemetryHub::new()
+            .with_timestamps(false)
+            .with_sequence_numbers(false);
+        let log = connect(&hub).await;
+        (hub, log)
+    }
+
+    /// Creates a processor with a client receiving its telemetry as bare
+    /// `[tag] payload` lines.
+    async fn processor() -> (CommandProcessor, Received) {
+        let (hub, log) = telemetry().await;
+        (CommandProcessor::new(hub), log)
+    }
+
+    /// Creates a processor on a mock clock, with a client receiving its
+    /// telemetry as bare `[tag] payload` lines.
+    async fn mock_processor() -> (CommandProcessor, MockClock, Received) {
+        let (hub, log) = telemetry().await;
+        let clock = MockClock::new();
+        let processor = CommandProcessor::new(hub).with_clock(Arc::new(clock.clone()));
+        (processor, clock, log)
+    }
+
+    /// Advances the clock and ticks the processor.
+    async fn advance(processor: &mut CommandProcessor, clock: &MockClock, by: Duration) {
+        clock.advance(by);
+        processor.tick().await;
+    }
+
+    /// An actuator that records each stage it fires, and when, and reports
+    /// whatever fault a test sets.
+    #[derive(Clone, Default)]
+    pub(crate) struct RecordingActuator {
+        /// The stages fired so far, with the time each fired.
+        pub(crate) fired: Arc<std::sync::Mutex<Vec<(String, Instant)>>>,
+        /// The fault reported to the processor, if any.
+        pub(crate) fault: Arc<std::sync::Mutex<Option<String>>>,
+    }
+
+    impl RecordingActuator {
+        /// Returns the stages fired so far.
+        pub(crate) fn stages(&self) -> Vec<String> {
+            let fired = self.fired.lock().unwrap();
+            fired.iter().map(|(stage, _)| stage.clone()).collect()
+        }
+    }
+
+    impl PropulsionActuator for RecordingActuator {
+        fn fire(&self, stage: &str) {
+            self.fired
+                .lock()
+                .unwrap()
+                .push((stage.to_string(), Instant::now()));
+        }
+
+        fn fault(&self) -> Option<String> {
+            self.fault.lock().unwrap().clone()
+        }
+    }
+
+    /// Drops the `, +<n>ms late` a fire reports from a telemetry line, since
+    /// it depends on how promptly the test's real-time sleeps wake.
+    fn without_jitter(line: String) -> String {
+        match line.rsplit_once(", +") {
+            Some((head, tail)) if tail.ends_with("ms late)") => format!("{})", head),
+            _ => line,
+        }
+    }
+
+    /// Parses a command from its JSON wire form.
+    fn command(json: &str) -> Command {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[tokio::test]
+    async fn queued_fires_ignite_in_deadline_order() {
+        let (mut processor, mut log) = processor().await;
+        let start = Instant::now();
+        processor.handle(command("0.2")).await;
+        processor.handle(command("0.1")).await;
+        assert_eq!(
+            next_line(&mut log).await,
+            "[🛰️ ⏳] Scheduled 'main' fire #1 in 0.20s"
+        );
+        assert_eq!(
+            next_line(&mut log).await,
+            "[🛰️ ⏳] Scheduled 'main' fire #2 in 0.10s"
+        );
+
+        let first = processor.next_deadline().unwrap();
+        assert!(first - start < Duration::from_millis(150));
+        time::sleep_until(first).await;
+        processor.tick().await;
+        assert_eq!(
+            without_jitter(next_line(&mut log).await),
+            "[🚀] Firing propulsion 'main' now! (priority 0)"
+        );
+
+        let second = processor.next_deadline().unwrap();
+        assert!(second - start >= Duration::from_millis(200));
+        time::sleep_until(second).await;
+        processor.tick().await;
+        assert_eq!(
+            without_jitter(next_line(&mut log).await),
+            "[🚀] Firing propulsion 'main' now! (priority 0)"
+        );
+        assert!(processor.next_deadline().is_none());
+    }
+
+    #[tokio::test]
+    async fn burn_runs_from_ignition_to_completion() {
+        let (mut processor, mut log) = processor().await;
+        processor
+            .handle(command(r#"{"type":"schedule","delay":0.1,"burn":0.2}"#))
+            .await;
+        assert_eq!(
+            next_line(&mut log).await,
+            "[🛰️ ⏳] Scheduled 'main' fire #1 in 0.10s, burning for 0.20s"
+        );
+
+        let ignition = processor.next_deadline().unwrap();
+        time::sleep_until(ignition).await;
+        processor.tick().await;
+        assert_eq!(
+            without_jitter(next_line(&mut log).await),
+            "[🚀] Firing propulsion 'main' now! (priority 0)"
+        );
+        assert_eq!(next_line(&mut log).await, "[🔥] thrust=100.0% on 'main'");
+
+        let end = processor.next_deadline().unwrap();
+        assert!(end - ignition >= Duration::from_millis(200));
+        time::sleep_until(end).await;
+        processor.tick().await;
+        assert_eq!(next_line(&mut log).await, "[🔥] thrust=100.0% on 'main'");
+        assert_eq!(next_line(&mut log).await, "[🏁] Burn 'main' complete");
+        assert!(processor.next_deadline().is_none());
+    }
+
+    #[tokio::test]
+    async fn cancel_during_a_burn_aborts_it() {
+        let (mut processor, mut log) = processor().await;
+        processor
+            .handle(command(r#"{"type":"schedule","delay":0.0,"burn":1.0}"#))
+            .await;
+        processor.tick().await;
+        next_line(&mut log).await;
+        assert_eq!(
+            without_jitter(next_line(&mut log).await),
+            "[🚀] Firing propulsion 'main' now! (priority 0)"
+        );
+        assert_eq!(next_line(&mut log).await, "[🔥] thrust=100.0% on 'main'");
+
+        processor.handle(command("-1")).await;
+        assert_eq!(next_line(&mut log).await, "[🛑] Cancelled fire command");
+        assert_eq!(next_line(&mut log).await, "[🛑] Burn aborted");
+        assert!(processor.next_deadline().is_none());
+    }
+
+    #[tokio::test]
+    async fn cancel_by_id_leaves_other_fires_armed() {
+        let (mut processor, mut log) = processor().await;
+        for (id, delay) in (1..).zip(["0.1", "0.12", "0.14"]) {
+            let outcome = processor.handle(command(delay)).await;
+            assert_eq!(outcome, CommandOutcome::Accepted { id: Some(id) });
+            next_line(&mut log).await;
+        }
+
+        let outcome = processor
+            .handle(command(r#"{"type":"cancel","id":2}"#))
+            .await;
+        assert_eq!(outcome, CommandOutcome::Accepted { id: Some(2) });
+        assert_eq!(next_line(&mut log).await, "[🛑] Cancelled fire #2");
+
+        time::sleep(Duration::from_millis(150)).await;
+        processor.tick().await;
+        assert_eq!(
+            without_jitter(next_line(&mut log).await),
+            "[🚀] Firing propulsion 'main' now! (priority 0)"
+        );
+        assert_eq!(
+            without_jitter(next_line(&mut log).await),
+            "[🚀] Firing propulsion 'main' now! (priority 0)"
+        );
+        assert!(processor.next_deadline().is_none());
+
+        let unknown = processor.handle(command(r#"{"cancel":2}"#)).await;
+        assert!(matches!(unknown, CommandOutcome::Rejected { .. }));
+        assert_eq!(
+            next_line(&mut log).await,
+            "[⚠️] No pending fire #2 to cancel"
+        );
+    }
+
+    #[test]
+    fn commands_round_trip_through_the_tagged_form() {
+        let commands = [
+            r#"{"type":"schedule","delay":2.0}"#,
+            r#"{"type":"schedule","delay":2.0,"burn":1.5}"#,
+            r#"{"type":"cancel"}"#,
+            r#"{"type":"cancel","id":7}"#,
+            r#"{"type":"abort"}"#,
+            r#"{"type":"clear"}"#,
+            r#"{"type":"status"}"#,
+        ];
+        for json in commands {
+            assert_eq!(serde_json::to_string(&command(json)).unwrap(), json);
+        }
+    }
+
+    #[test]
+    fn legacy_forms_map_onto_commands() {
+        assert_eq!(
+            command("2.0"),
+            Command::Schedule {
+                when: FireTime::Delay(2.0),
+                unit: DelayUnit::Seconds,
+                stage: DEFAULT_STAGE.to_string(),
+                burn: 0.0,
+                priority: 0,
+            }
+        );
+        assert_eq!(
+            command(r#"{"delay":2.0,"burn":1.5}"#),
+            Command::Schedule {
+                when: FireTime::Delay(2.0),
+                unit: DelayUnit::Seconds,
+                stage: DEFAULT_STAGE.to_string(),
+                burn: 1.5,
+                priority: 0,
+            }
+        );
+        assert_eq!(
+            command("-1"),
+            Command::Cancel {
+                id: None,
+                stage: None
+            }
+        );
+        assert_eq!(
+            command(r#"{"cancel":7}"#),
+            Command::Cancel {
+                id: Some(7),
+                stage: None
+            }
+        );
+    }
+
+    #[test]
+    fn malformed_commands_report_their_own_form() {
+        let error = |json| {
+            serde_json::from_str::<Command>(json)
+                .unwrap_err()
+                .to_string()
+        };
+        assert!(error(r#"{"type":"launch"}"#).contains("unknown variant `launch`"));
+        assert!(error(r#"{"delay":"soon"}"#).contains("invalid type: string"));
+        assert!(error(r#"{"cancel":-7}"#).contains("invalid value: integer `-7`"));
+        assert!(error(r#""2.0""#).contains("expected a command object or a bare delay"));
+    }
+
+    #[tokio::test]
+    async fn status_reports_pending_fires_and_burns() {
+        let (mut processor, mut log) = processor().await;
+        processor.handle(command(r#"{"type":"status"}"#)).await;
+        assert_eq!(
+            next_line(&mut log).await,
+            "[📋] Status: idle (commands=1, scheduled=0, cancelled=0, invalid=0, fired=0)"
+        );
+
+        processor.handle(command("1.3")).await;
+        processor.handle(command("4.0")).await;
+        processor.handle(command(r#"{"type":"status"}"#)).await;
+        assert_eq!(
+            wait_for(&mut log, "Status").await,
+            "[📋] Status: 2 pending [#1 'main' in 1.3s, #2 'main' in 4.0s] \
+             (commands=4, scheduled=2, cancelled=0, invalid=0, fired=0)"
+        );
+
+        processor
+            .handle(command(r#"{"delay":0.0,"burn":2.0}"#))
+            .await;
+        processor.tick().await;
+        let later = Instant::now() + Duration::from_secs(1);
+        assert_eq!(
+            processor.status_summary(later),
+            "Status: 2 pending [#1 'main' in 0.3s, #2 'main' in 3.0s], burning [#3 'main' for 1.0s]"
+        );
+    }
+
+    #[tokio::test]
+    async fn countdown_precedes_a_two_second_fire() {
+        let (mut processor, mut log) = processor().await;
+        processor.handle(command("2.0")).await;
+        while let Some(deadline) = processor.next_deadline() {
+            time::sleep_until(deadline).await;
+            processor.tick().await;
+        }
+
+        let sequence = [
+            "[🛰️ ⏳] Scheduled 'main' fire #1 in 2.00s",
+            "[⏳] T-1s to fire #1",
+            "[🚀] Firing propulsion 'main' now! (priority 0)",
+        ];
+        for expected in sequence {
+            assert_eq!(without_jitter(next_line(&mut log).await), expected);
+        }
+    }
+
+    #[test]
+    fn countdown_starts_below_the_delay_and_skips_short_ones() {
+        assert_eq!(countdown_start(1.5), 0);
+        assert_eq!(countdown_start(2.0), 1);
+        assert_eq!(countdown_start(3.0), 2);
+        assert_eq!(countdown_start(3.2), 3);
+        assert_eq!(countdown_start(60.0), COUNTDOWN_FROM_SECS);
+    }
+
+    #[tokio::test]
+    async fn delays_outside_the_bounds_are_rejected() {
+        let (processor, mut log) = processor().await;
+        let mut processor = processor.with_delay_bounds(1.0, 10.0);
+
+        processor.handle(command("0.5")).await;
+        assert_eq!(
+            next_line(&mut log).await,
+            "[⚠️] Delay 0.5s is below the minimum of 1s"
+        );
+        processor.handle(command("11.0")).await;
+        assert_eq!(
+            next_line(&mut log).await,
+            "[⚠️] Delay 11s exceeds the maximum of 10s"
+        );
+        let accepted = processor.handle(command("1.5")).await;
+        assert_eq!(accepted, CommandOutcome::Accepted { id: Some(1) });
+    }
+
+    #[tokio::test]
+    async fn non_finite_delays_are_rejected_with_a_warning() {
+        let hub = TelemetryHub::new().with_format(TelemetryFormat::Json);
+        let mut log = connect(&hub).await;
+        let mut processor = CommandProcessor::new(hub);
+
+        for delay in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let outcome = processor
+                .handle(Command::Schedule {
+                    when: FireTime::Delay(delay),
+                    unit: DelayUnit::Seconds,
+                    stage: DEFAULT_STAGE.to_string(),
+                    burn: 0.0,
+                    priority: 0,
+                })
+                .await;
+            let reason = format!("Invalid non-finite delay value: {}", delay);
+            assert_eq!(
+                outcome,
+                CommandOutcome::Rejected {
+                    reason: reason.clone()
+                }
+            );
+            let message: TelemetryMessage =
+                serde_json::from_str(&next_line(&mut log).await).unwrap();
+            assert_eq!(
+                (message.severity, message.payload),
+                (Severity::Warn, reason)
+            );
+        }
+        assert!(processor.next_deadline().is_none());
+    }
+
+    #[tokio::test]
+    async fn persisted_schedule_survives_a_restart() {
+        let path =
+            std::env::temp_dir().join(format!("prop-demo-{}-schedule.json", std::process::id()));
+        {
+            let (processor, _log) = processor().await;
+            let mut processor = processor.with_persistence(Some(path.clone())).await;
+            processor
+                .handle(command(r#"{"delay":30.0,"burn":2.0}"#))
+                .await;
+        }
+
+        let (restarted, mut log) = processor().await;
+        let mut restarted = restarted.with_persistence(Some(path.clone())).await;
+        let restored = wait_for(&mut log, "Restored").await;
+        let _ = std::fs::remove_file(&path);
+
+        let remaining: f64 = restored
+            .strip_prefix("[🛰️ ⏳] Restored fire #1 in ")
+            .and_then(|rest| rest.strip_suffix('s'))
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!((29.0..=30.0).contains(&remaining), "{}", restored);
+        let next = restarted.handle(command("60.0")).await;
+        assert_eq!(next, CommandOutcome::Accepted { id: Some(2) });
+    }
+
+    #[tokio::test]
+    async fn persistence_failure_is_reported_as_a_warning() {
+        let hub = TelemetryHub::new().with_format(TelemetryFormat::Json);
+        let mut log = connect(&hub).await;
+        let path = std::env::temp_dir()
+            .join(format!("prop-demo-{}-missing", std::process::id()))
+            .join("schedule.json");
+        let mut processor = CommandProcessor::new(hub)
+            .with_persistence(Some(path))
+            .await;
+
+        let outcome = processor.handle(command("5.0")).await;
+        assert_eq!(outcome, CommandOutcome::Accepted { id: Some(1) });
+        let message: TelemetryMessage = serde_json::from_str(&next_line(&mut log).await).unwrap();
+        assert_eq!(message.severity, Severity::Warn);
+        assert!(
+            message
+                .payload
+                .starts_with("Failed to persist schedule to "),
+            "{}",
+            message.payload
+        );
+    }
+
+    #[tokio::test]
+    async fn abort_locks_out_schedules_until_cleared() {
+        let (mut processor, mut log) = processor().await;
+        processor.handle(command("5.0")).await;
+
+        processor.handle(command(r#"{"type":"abort"}"#)).await;
+        assert_eq!(wait_for(&mut log, "ABORT").await, "[🛑] ABORT");
+        assert!(processor.next_deadline().is_none());
+        let locked = processor.handle(command("1.0")).await;
+        let reason = "Aborted, scheduling is locked out until cleared".to_string();
+        assert_eq!(locked, CommandOutcome::Rejected { reason });
+
+        processor.handle(command(r#"{"type":"clear"}"#)).await;
+        let rearmed = processor.handle(command("1.0")).await;
+        assert_eq!(rearmed, CommandOutcome::Accepted { id: Some(2) });
+    }
+
+    #[tokio::test]
+    async fn metrics_count_each_kind_of_command() {
+        let (mut processor, _log) = processor().await;
+        for json in ["0.05", "2.0", "-5.0", r#"{"cancel":2}"#, r#"{"cancel":9}"#] {
+            processor.handle(command(json)).await;
+        }
+        processor.invalid("invalid command: eof".to_string()).await;
+        time::sleep_until(processor.next_deadline().unwrap()).await;
+        processor.tick().await;
+
+        assert_eq!(
+            processor.metrics(),
+            ProcessorMetrics {
+                commands_received: 5,
+                schedules_accepted: 2,
+                cancels: 1,
+                invalid_commands: 3,
+                fires_executed: 1,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn history_records_commands_in_order() {
+        let (mut processor, _log) = processor().await;
+        let commands = ["1.0", r#"{"cancel":1}"#, "-5.0"];
+        for json in commands {
+            processor.handle(command(json)).await;
+        }
+
+        let history = processor.history();
+        let recorded: Vec<_> = history.iter().map(|entry| entry.command.clone()).collect();
+        assert_eq!(recorded, commands.map(command));
+        assert_eq!(history[0].outcome, CommandOutcome::Accepted { id: Some(1) });
+        assert_eq!(history[1].outcome, CommandOutcome::Accepted { id: Some(1) });
+        assert!(matches!(
+            history[2].outcome,
+            CommandOutcome::Rejected { .. }
+        ));
+        assert!(history.windows(2).all(|pair| pair[0].at <= pair[1].at));
+    }
+
+    #[tokio::test]
+    async fn delays_are_normalized_from_their_unit() {
+        let (mut processor, mut log) = processor().await;
+        let units = [
+            (r#"{"type":"schedule","delay":1.5}"#, "1.50s"),
+            (r#"{"type":"schedule","delay":1.5,"unit":"s"}"#, "1.50s"),
+            (r#"{"type":"schedule","delay":50,"unit":"ms"}"#, "0.05s"),
+            (r#"{"type":"schedule","delay":2,"unit":"min"}"#, "120.00s"),
+        ];
+        for (id, (json, secs)) in (1..).zip(units) {
+            processor.handle(command(json)).await;
+            let expected = format!("[🛰️ ⏳] Scheduled 'main' fire #{} in {}", id, secs);
+            assert_eq!(next_line(&mut log).await, expected);
+        }
+
+        time::sleep_until(processor.next_deadline().unwrap()).await;
+        processor.tick().await;
+        assert!(next_line(&mut log).await.contains("Firing propulsion"));
+        assert_eq!(processor.metrics().fires_executed, 1);
+    }
+
+    #[tokio::test]
+    async fn stages_are_scheduled_and_cancelled_independently() {
+        let (mut processor, mut log) = processor().await;
+        for stage in ["main", "aux", "aux"] {
+            let json = format!(r#"{{"type":"schedule","delay":0.1,"stage":"{}"}}"#, stage);
+            processor.handle(command(&json)).await;
+        }
+        processor
+            .handle(command(r#"{"type":"cancel","stage":"aux"}"#))
+            .await;
+        assert_eq!(
+            wait_for(&mut log, "Cancelled").await,
+            "[🛑] Cancelled 'aux' fire commands"
+        );
+
+        processor
+            .handle(command(r#"{"type":"schedule","delay":0.1,"stage":"aux"}"#))
+            .await;
+        while processor.next_deadline().is_some() {
+            time::sleep_until(processor.next_deadline().unwrap()).await;
+            processor.tick().await;
+        }
+        assert!(wait_for(&mut log, "Firing").await.contains("'main' now!"));
+        assert!(next_line(&mut log).await.contains("'aux' now!"));
+        assert_eq!(processor.metrics().fires_executed, 2);
+    }
+
+    #[test]
+    fn misspelt_fields_are_rejected() {
+        let error =
+            serde_json::from_str::<Command>(r#"{"type":"schedule","delay":1.0,"stgae":"aux"}"#)
+                .unwrap_err();
+        assert!(
+            error.to_string().contains("unknown field `stgae`"),
+            "{}",
+            error
+        );
+    }
+
+    #[tokio::test]
+    async fn absolute_fire_times_are_converted_or_rejected() {
+        let (mut processor, mut log) = processor().await;
+        let now_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+
+        let start = Instant::now();
+        let json = format!(r#"{{"type":"schedule","at_ms":{}}}"#, now_ms + 200);
+        let outcome = processor.handle(command(&json)).await;
+        assert_eq!(outcome, CommandOutcome::Accepted { id: Some(1) });
+        let lead = processor.next_deadline().unwrap() - start;
+        assert!(lead > Duration::from_millis(150) && lead <= Duration::from_millis(200));
+        time::sleep_until(processor.next_deadline().unwrap()).await;
+        processor.tick().await;
+        assert!(wait_for(&mut log, "Firing").await.contains("'main' now!"));
+
+        let past = now_ms - 1000;
+        let json = format!(r#"{{"type":"schedule","at_ms":{}}}"#, past);
+        processor.handle(command(&json)).await;
+        assert_eq!(
+            next_line(&mut log).await,
+            format!("[⚠️] Fire time {}ms is in the past", past)
+        );
+    }
+
+    #[test]
+    fn delay_and_at_ms_are_mutually_exclusive() {
+        let error = |json| {
+            serde_json::from_str::<Command>(json)
+                .unwrap_err()
+                .to_string()
+        };
+        assert!(
+            error(r#"{"type":"schedule","delay":1.0,"at_ms":5}"#).contains("mutually exclusive")
+        );
+        assert!(error(r#"{"type":"schedule"}"#).contains("missing field `delay` or `at_ms`"));
+    }
+
+    #[tokio::test]
+    async fn paused_fires_keep_their_remaining_time() {
+        let (mut processor, mut log) = processor().await;
+        processor.handle(command("0.3")).await;
+        time::sleep(Duration::from_millis(100)).await;
+        processor.handle(command(r#"{"type":"pause"}"#)).await;
+
+        time::sleep(Duration::from_millis(300)).await;
+        processor.tick().await;
+        assert_eq!(processor.metrics().fires_executed, 0);
+        processor.handle(command(r#"{"type":"resume"}"#)).await;
+        let resumed = wait_for(&mut log, "resumed").await;
+        assert!(resumed.ends_with("re-armed 1 pending fires"), "{}", resumed);
+
+        let remaining = processor.next_deadline().unwrap() - Instant::now();
+        assert!(remaining > Duration::from_millis(150), "{:?}", remaining);
+        assert!(remaining <= Duration::from_millis(200), "{:?}", remaining);
+    }
+
+    #[tokio::test]
+    async fn fire_overdue_at_the_pause_fires_on_resume() {
+        let (mut processor, mut log) = processor().await;
+        processor.handle(command("0.05")).await;
+        time::sleep(Duration::from_millis(100)).await;
+        processor.handle(command(r#"{"type":"pause"}"#)).await;
+        time::sleep(Duration::from_millis(100)).await;
+        processor.tick().await;
+        assert_eq!(processor.metrics().fires_executed, 0);
+
+        processor.handle(command(r#"{"type":"resume"}"#)).await;
+        assert!(processor.next_deadline().unwrap() <= Instant::now());
+        processor.tick().await;
+
+        assert!(wait_for(&mut log, "Firing").await.contains("'main' now!"));
+    }
+
+    #[tokio::test]
+    async fn fires_scheduled_while_paused_keep_their_full_delay() {
+        let (mut processor, mut log) = processor().await;
+        processor.handle(command(r#"{"type":"pause"}"#)).await;
+        time::sleep(Duration::from_millis(100)).await;
+        processor.handle(command("5.0")).await;
+        time::sleep(Duration::from_millis(100)).await;
+        processor.handle(command(r#"{"type":"resume"}"#)).await;
+        processor.handle(command(r#"{"type":"status"}"#)).await;
+
+        let status = wait_for(&mut log, "Status").await;
+        assert!(status.contains("[#1 'main' in 5.0s]"), "{}", status);
+    }
+
+    #[tokio::test]
+    async fn actuator_fires_at_the_deadline() {
+        let (processor, mut log) = processor().await;
+        let actuator = RecordingActuator::default();
+        let mut processor = processor.with_actuator(Arc::new(actuator.clone()));
+        processor
+            .handle(command(r#"{"type":"schedule","delay":0.1,"stage":"aux"}"#))
+            .await;
+        let deadline = processor.next_deadline().unwrap();
+
+        processor.tick().await;
+        assert!(actuator.stages().is_empty());
+        time::sleep_until(deadline).await;
+        processor.tick().await;
+
+        let fired = actuator.fired.lock().unwrap().clone();
+        assert_eq!(actuator.stages(), ["aux"]);
+        assert!(fired[0].1 >= deadline);
+        assert!(wait_for(&mut log, "Firing").await.contains("'aux' now!"));
+    }
+
+    #[tokio::test]
+    async fn dry_run_marks_fires_without_actuating() {
+        let (processor, mut log) = processor().await;
+        let actuator = RecordingActuator::default();
+        let mut processor = processor
+            .with_actuator(Arc::new(actuator.clone()))
+            .with_dry_run(true);
+        processor
+            .handle(command(r#"{"delay":0.05,"burn":0.05}"#))
+            .await;
+        while let Some(deadline) = processor.next_deadline() {
+            time::sleep_until(deadline).await;
+            processor.tick().await;
+        }
+
+        assert_eq!(
+            without_jitter(wait_for(&mut log, "Firing").await),
+            "[🚀] [DRYRUN] Firing propulsion 'main' now! (priority 0)"
+        );
+        assert_eq!(
+            wait_for(&mut log, "Burn").await,
+            "[🏁] [DRYRUN] Burn 'main' complete"
+        );
+        assert!(actuator.stages().is_empty());
+        assert_eq!(processor.metrics().fires_executed, 1);
+    }
+
+    #[tokio::test]
+    async fn fires_are_logged_with_their_stage() {
+        crate::log::tests::capture();
+        let (mut processor, _log) = processor().await;
+        processor
+            .handle(command(r#"{"type":"schedule","delay":0.0,"stage":"aux"}"#))
+            .await;
+        processor.tick().await;
+
+        let fired: Vec<_> = crate::log::tests::captured()
+            .into_iter()
+            .filter(|event| event.message == "propulsion fired")
+            .collect();
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].level, crate::log::Level::Info);
+        assert_eq!(fired[0].field("id"), Some("1"));
+        assert_eq!(fired[0].field("stage"), Some("aux"));
+        assert_eq!(fired[0].field("dry_run"), Some("false"));
+    }
+
+    #[tokio::test]
+    async fn restored_snapshot_fires_after_its_remaining_time() {
+        let (mut original, _log) = processor().await;
+        original
+            .handle(command(r#"{"type":"schedule","delay":2.0}"#))
+            .await;
+        let json = serde_json::to_string(&original.snapshot()).unwrap();
+
+        let (hub, mut log) = telemetry().await;
+        let snapshot = serde_json::from_str(&json).unwrap();
+        let restored_at = Instant::now();
+        let mut restored = CommandProcessor::from_snapshot(snapshot, hub).unwrap();
+        let fires = restored.snapshot().fires;
+        assert_eq!(fires.len(), 1);
+        assert_eq!((fires[0].id, fires[0].stage.as_str()), (1, DEFAULT_STAGE));
+        assert!((fires[0].remaining_secs - 2.0).abs() < 0.1, "{:?}", fires);
+
+        time::sleep_until(restored.next_deadline().unwrap()).await;
+        restored.tick().await;
+        wait_for(&mut log, "Firing").await;
+        let elapsed = restored_at.elapsed();
+        assert!(
+            elapsed >= Duration::from_millis(1900) && elapsed < Duration::from_millis(2200),
+            "{:?}",
+            elapsed
+        );
+    }
+
+    #[tokio::test]
+    async fn snapshot_with_invalid_times_is_rejected() {
+        for remaining_secs in [f64::NAN, f64::INFINITY, 1e30] {
+            let snapshot = ProcessorSnapshot {
+                next_id: 2,
+                fires: vec![FireSnapshot {
+                    id: 1,
+                    stage: DEFAULT_STAGE.to_string(),
+                    remaining_secs,
+                    burn_secs: 0.0,
+                    priority: 0,
+                }],
+                burns: Vec::new(),
+                aborted: false,
+                paused: false,
+                metrics: ProcessorMetrics::default(),
+            };
+            match CommandProcessor::from_snapshot(snapshot, TelemetryHub::new()) {
+                Err(FlightComputerError::SchedulingError(reason)) => assert_eq!(
+                    reason,
+                    format!(
+                        "snapshot fire #1 has an invalid time of {} seconds",
+                        remaining_secs
+                    )
+                ),
+                Err(e) => panic!("expected SchedulingError, got {}", e),
+                Ok(_) => panic!("restored a fire {}s out", remaining_secs),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn persisted_fire_with_an_invalid_burn_is_dropped() {
+        let path =
+            std::env::temp_dir().join(format!("prop-demo-{}-invalid.json", std::process::id()));
+        let fire_at_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis()
+            + 60_000;
+        let saved = format!(
+            r#"{{"next_id":2,"fires":[{{"id":1,"fire_at_ms":{},"burn_secs":1e30}}]}}"#,
+            fire_at_ms
+        );
+        std::fs::write(&path, saved).unwrap();
+
+        let (processor, mut log) = processor().await;
+        let processor = processor.with_persistence(Some(path.clone())).await;
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(
+            next_line(&mut log).await,
+            "[⚠️] Dropped fire #1 with an invalid time or burn duration"
+        );
+        assert!(processor.snapshot().fires.is_empty());
+    }
+
+    #[tokio::test]
+    async fn higher_priority_fires_first_at_the_same_deadline() {
+        let (mut processor, mut log) = processor().await;
+        // Fires scheduled while paused share the pause as their start, so
+        // these three come due at the identical instant.
+        processor.handle(command(r#"{"type":"pause"}"#)).await;
+        for (stage, priority) in [("low", 1), ("abort-burn", 9), ("also-low", 1)] {
+            let json = format!(
+                r#"{{"type":"schedule","delay":0.05,"stage":"{}","priority":{}}}"#,
+                stage, priority
+            );
+            processor.handle(command(&json)).await;
+        }
+        processor.handle(command(r#"{"type":"resume"}"#)).await;
+        time::sleep_until(processor.next_deadline().unwrap()).await;
+        processor.tick().await;
+
+        assert_eq!(
+            without_jitter(wait_for(&mut log, "Firing").await),
+            "[🚀] Firing propulsion 'abort-burn' now! (priority 9)"
+        );
+        assert_eq!(
+            without_jitter(next_line(&mut log).await),
+            "[🚀] Firing propulsion 'low' now! (priority 1)"
+        );
+        assert_eq!(
+            without_jitter(next_line(&mut log).await),
+            "[🚀] Firing propulsion 'also-low' now! (priority 1)"
+        );
+    }
+
+    #[tokio::test]
+    async fn earlier_deadline_fires_first_when_both_come_due_in_one_tick() {
+        let (mut processor, mut log) = processor().await;
+        processor
+            .handle(command(
+                r#"{"type":"schedule","delay":0.1,"stage":"urgent","priority":9}"#,
+            ))
+            .await;
+        processor
+            .handle(command(
+                r#"{"type":"schedule","delay":0.05,"stage":"early"}"#,
+            ))
+            .await;
+        time::sleep(Duration::from_millis(150)).await;
+        processor.tick().await;
+
+        assert_eq!(
+            without_jitter(wait_for(&mut log, "Firing").await),
+            "[🚀] Firing propulsion 'early' now! (priority 0)"
+        );
+        assert_eq!(
+            without_jitter(next_line(&mut log).await),
+            "[🚀] Firing propulsion 'urgent' now! (priority 9)"
+        );
+    }
+
+    #[tokio::test]
+    async fn burn_samples_follow_the_thrust_curve() {
+        let (processor, mut log) = processor().await;
+        let mut processor = processor.with_thrust_profile(ThrustProfile {
+            peak_percent: 100.0,
+            ramp_up: Duration::from_millis(500),
+            ramp_down: Duration::from_millis(250),
+            sample_interval: Duration::from_millis(250),
+        });
+        processor
+            .handle(command(r#"{"delay":0.0,"burn":1.0}"#))
+            .await;
+        while let Some(deadline) = processor.next_deadline() {
+            time::sleep_until(deadline).await;
+            processor.tick().await;
+        }
+
+        let mut samples = Vec::new();
+        while samples.len() < 5 {
+            let line = wait_for(&mut log, "thrust=").await;
+            let percent = line
+                .strip_prefix("[🔥] thrust=")
+                .and_then(|rest| rest.strip_suffix("% on 'main'"))
+                .unwrap();
+            samples.push(percent.parse::<f64>().unwrap());
+        }
+        assert_eq!(samples, [0.0, 50.0, 100.0, 100.0, 0.0]);
+        assert_eq!(next_line(&mut log).await, "[🏁] Burn 'main' complete");
+    }
+
+    #[tokio::test]
+    async fn fire_reports_the_induced_jitter() {
+        let (mut processor, mut log) = processor().await;
+        processor
+            .handle(command(r#"{"type":"schedule","delay":0.05,"priority":4}"#))
+            .await;
+        let deadline = processor.next_deadline().unwrap();
+
+        time::sleep_until(deadline + Duration::from_millis(37)).await;
+        processor.tick().await;
+
+        let fire = wait_for(&mut log, "Firing").await;
+        let late = fire
+            .strip_prefix("[🚀] Firing propulsion 'main' now! (priority 4, +")
+            .and_then(|rest| rest.strip_suffix("ms late)"))
+            .and_then(|late| late.parse::<u64>().ok());
+        assert!(matches!(late, Some(37..=60)), "{}", fire);
+    }
+
+    #[tokio::test]
+    async fn fires_beyond_the_horizon_are_rejected() {
+        let (processor, mut log) = processor().await;
+        let mut processor = processor.with_max_horizon(Duration::from_secs(60));
+
+        processor.handle(command("90.0")).await;
+        assert_eq!(
+            next_line(&mut log).await,
+            "[⚠️] Fire in 90s is beyond the scheduling horizon of 60s"
+        );
+
+        let at_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis()
+            + 120_000;
+        let json = format!(r#"{{"type":"schedule","at_ms":{}}}"#, at_ms);
+        let outcome = processor.handle(command(&json)).await;
+        assert!(matches!(outcome, CommandOutcome::Rejected { .. }));
+        let rejected = next_line(&mut log).await;
+        assert!(rejected.starts_with("[⚠️] Fire in 119."), "{}", rejected);
+        assert!(rejected.ends_with("s is beyond the scheduling horizon of 60s"));
+
+        let within = processor.handle(command("30.0")).await;
+        assert_eq!(within, CommandOutcome::Accepted { id: Some(1) });
+    }
+
+    #[tokio::test]
+    async fn time_until_next_fire_covers_idle_pending_and_overdue() {
+        let (mut processor, _log) = processor().await;
+        assert_eq!(processor.time_until_next_fire(), None);
+
+        // Fires scheduled while paused are measured from the pause, exactly.
+        processor.handle(command(r#"{"type":"pause"}"#)).await;
+        processor.handle(command("5.0")).await;
+        processor.handle(command("2.0")).await;
+        time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(
+            processor.time_until_next_fire(),
+            Some(Duration::from_secs(2))
+        );
+
+        processor.handle(command(r#"{"type":"resume"}"#)).await;
+        time::sleep(Duration::from_millis(20)).await;
+        let remaining = processor.time_until_next_fire().unwrap();
+        assert!(
+            remaining < Duration::from_millis(1990) && remaining > Duration::from_millis(1500),
+            "{:?}",
+            remaining
+        );
+
+        processor.handle(command("0.01")).await;
+        time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(processor.time_until_next_fire(), Some(Duration::ZERO));
+    }
+
+    #[tokio::test]
+    async fn fires_overdue_beyond_the_tolerance_expire() {
+        let (processor, mut log) = processor().await;
+        let actuator = RecordingActuator::default();
+        let mut processor = processor
+            .with_stale_tolerance(Some(Duration::from_millis(100)))
+            .with_actuator(Arc::new(actuator.clone()));
+        processor
+            .handle(command(
+                r#"{"type":"schedule","delay":0.05,"stage":"stale"}"#,
+            ))
+            .await;
+        time::sleep(Duration::from_millis(300)).await;
+        processor.tick().await;
+        let expired = wait_for(&mut log, "expired").await;
+        assert!(
+            expired.starts_with("[⚠️] Fire #1 expired, was 0.2") && expired.ends_with("s overdue"),
+            "{}",
+            expired
+        );
+
+        processor
+            .handle(command(
+                r#"{"type":"schedule","delay":0.05,"stage":"late"}"#,
+            ))
+            .await;
+        time::sleep(Duration::from_millis(100)).await;
+        processor.tick().await;
+        let fire = wait_for(&mut log, "Firing").await;
+        assert!(
+            fire.starts_with("[🚀] Firing propulsion 'late' now!"),
+            "{}",
+            fire
+        );
+        assert_eq!(actuator.stages(), ["late"]);
+        assert_eq!(processor.metrics().fires_executed, 1);
+    }
+
+    #[tokio::test]
+    async fn fire_triggers_exactly_at_the_deadline_tick() {
+        let (mut processor, clock, mut log) = mock_processor().await;
+        processor.handle(command("1.0")).await;
+
+        advance(&mut processor, &clock, Duration::from_millis(999)).await;
+        assert_eq!(processor.metrics().fires_executed, 0);
+        advance(&mut processor, &clock, Duration::from_millis(1)).await;
+
+        assert_eq!(processor.metrics().fires_executed, 1);
+        assert!(wait_for(&mut log, "Firing").await.ends_with("+0ms late)"));
+    }
+
+    #[tokio::test]
+    async fn latched_fault_rejects_schedules_until_cleared() {
+        let (processor, mut log) = processor().await;
+        let actuator = RecordingActuator::default();
+        let mut processor = processor.with_actuator(Arc::new(actuator.clone()));
+        *actuator.fault.lock().unwrap() = Some("valve stuck".to_string());
+
+        let rejected = processor.handle(command("1.0")).await;
+        let reason = "Actuator fault latched (valve stuck), scheduling is locked out until cleared";
+        assert_eq!(
+            rejected,
+            CommandOutcome::Rejected {
+                reason: reason.to_string()
+            }
+        );
+        assert_eq!(
+            next_line(&mut log).await,
+            "[⚠️] Actuator fault latched: valve stuck"
+        );
+        assert_eq!(next_line(&mut log).await, format!("[⚠️] {}", reason));
+
+        *actuator.fault.lock().unwrap() = None;
+        let cleared = processor.handle(command(r#"{"type":"clear_fault"}"#)).await;
+        assert_eq!(cleared, CommandOutcome::accepted());
+        assert_eq!(
+            next_line(&mut log).await,
+            "[✅] Actuator fault cleared, scheduling enabled"
+        );
+        let accepted = processor.handle(command("1.0")).await;
+        assert_eq!(accepted, CommandOutcome::Accepted { id: Some(1) });
+
+        let nothing = processor.handle(command(r#"{"type":"clear_fault"}"#)).await;
+        assert!(matches!(nothing, CommandOutcome::Rejected { .. }));
+    }
+
+    #[tokio::test]
+    async fn custom_fire_tag_is_used() {
+        let (processor, clock, mut log) = mock_processor().await;
+        let tags = TelemetryTags {
+            fire: "IGNITION".to_string(),
+            ..TelemetryTags::default()
+        };
+        let mut processor = processor.with_tags(tags);
+        processor.handle(command("0.5")).await;
+        advance(&mut processor, &clock, Duration::from_millis(500)).await;
+
+        assert_eq!(
+            next_line(&mut log).await,
+            "[🛰️ ⏳] Scheduled 'main' fire #1 in 0.50s"
+        );
+        assert_eq!(
+            next_line(&mut log).await,
+            "[IGNITION] Firing propulsion 'main' now! (priority 0, +0ms late)"
+        );
+    }
+
+    #[tokio::test]
+    async fn rapid_identical_schedules_coalesce() {
+        let (processor, clock, mut log) = mock_processor().await;
+        let mut processor = processor.with_dedupe_window(Some(DEFAULT_DEDUPE_WINDOW));
+        let schedule = r#"{"type":"schedule","delay":5.0,"stage":"aux","priority":2}"#;
+
+        for _ in 0..3 {
+            let outcome = processor.handle(command(schedule)).await;
+            assert_eq!(outcome, CommandOutcome::Accepted { id: Some(1) });
+            clock.advance(Duration::from_millis(20));
+        }
+        processor
+            .handle(command(r#"{"type":"schedule","delay":5.0,"stage":"aux"}"#))
+            .await;
+
+        next_line(&mut log).await;
+        for _ in 0..2 {
+            assert_eq!(
+                next_line(&mut log).await,
+                "[🛰️ ⏳] Coalesced duplicate 'aux' schedule into fire #1"
+            );
+        }
+        let ids: Vec<u64> = processor
+            .snapshot()
+            .fires
+            .iter()
+            .map(|fire| fire.id)
+            .collect();
+        assert_eq!(ids, [1, 2]);
+    }
+
+    #[tokio::test]
+    async fn reset_returns_to_an_idle_processor() {
+        let (processor, mut log) = processor().await;
+        let mut processor = processor.with_delay_bounds(0.5, 60.0);
+        processor.handle(command("5.0")).await;
+        processor.handle(command("10.0")).await;
+        processor.handle(command(r#"{"type":"abort"}"#)).await;
+        processor.handle(command(r#"{"type":"pause"}"#)).await;
+
+        processor.handle(command(r#"{"type":"reset"}"#)).await;
+        assert_eq!(
+            wait_for(&mut log, "reset").await,
+            "[🔄] System reset to idle"
+        );
+        assert!(processor.next_deadline().is_none());
+        assert_eq!(processor.metrics().commands_received, 5);
+
+        let accepted = processor.handle(command("1.0")).await;
+        assert_eq!(accepted, CommandOutcome::Accepted { id: Some(3) });
+        assert_eq!(processor.delay_bounds(), (0.5, 60.0));
+        processor
+            .handle(command(r#"{"type":"reset","clear_metrics":true}"#))
+            .await;
+        assert_eq!(processor.metrics(), ProcessorMetrics::default());
+        assert!(processor.snapshot().fires.is_empty());
+    }
+
+    #[tokio::test]
+    async fn unrepresentable_delays_and_burns_are_rejected_with_a_warning() {
+        let hub = TelemetryHub::new().with_format(TelemetryFormat::Json);
+        let mut log = connect(&hub).await;
+        let mut processor = CommandProcessor::new(hub).with_delay_bounds(0.0, f64::MAX);
+
+        // 1e19s passes the horizon check and fits a `Duration`, but not past
+        // the latest `Instant`; 1e300s does not even fit a `Duration`.
+        let cases = [
+            (
+                1e19,
+                0.0,
+                format!("Delay {}s is too far in the future to schedule", 1e19),
+            ),
+            (
+                1.0,
+                1e19,
+                format!("Burn duration {}s is too long to schedule", 1e19),
+            ),
+            (
+                1.0,
+                1e300,
+                format!("Burn duration {}s is too long to schedule", 1e300),
+            ),
+            (
+                1.0,
+                f64::NAN,
+                "Invalid non-finite burn duration: NaN".to_string(),
+            ),
+            (
+                1.0,
+                f64::INFINITY,
+                "Invalid non-finite burn duration: inf".to_string(),
+            ),
+        ];
+        for (delay, burn, reason) in cases {
+            let outcome = processor
+                .handle(Command::Schedule {
+                    when: FireTime::Delay(delay),
+                    unit: DelayUnit::Seconds,
+                    stage: DEFAULT_STAGE.to_string(),
+                    burn,
+                    priority: 0,
+                })
+                .await;
+            assert_eq!(
+                outcome,
+                CommandOutcome::Rejected {
+                    reason: reason.clone()
+                }
+            );
+            let message: TelemetryMessage =
+                serde_json::from_str(&next_line(&mut log).await).unwrap();
+            assert_eq!(
+                (message.severity, message.payload),
+                (Severity::Warn, reason)
+            );
+        }
+        assert!(processor.next_deadline().is_none());
+        assert_eq!(processor.metrics().invalid_commands, 5);
+    }
+
+    #[tokio::test]
+    async fn fire_whose_burn_would_end_past_the_latest_instant_is_dropped() {
+        let (processor, clock, mut log) = mock_processor().await;
+        let actuator = RecordingActuator::default();
+        let mut processor = processor
+            .with_delay_bounds(0.0, f64::MAX)
+            .with_actuator(Arc::new(actuator.clone()));
+        // The most whole seconds that can still be added to the clock.
+        let headroom = (0..64).rev().fold(0u64, |secs, bit| {
+            let candidate = secs | 1 << bit;
+            let fits = clock.now().checked_add(Duration::from_secs(candidate));
+            if fits.is_some() { candidate } else { secs }
+        });
+        // Leave room for the rounding to f64, but not for a late ignition.
+        let burn = headroom.saturating_sub(4096) as f64;
+        let outcome = processor
+            .handle(Command::Schedule {
+                when: FireTime::Delay(1.0),
+                unit: DelayUnit::Seconds,
+                stage: DEFAULT_STAGE.to_string(),
+                burn,
+                priority: 0,
+            })
+            .await;
+        assert_eq!(outcome, CommandOutcome::Accepted { id: Some(1) });
+
+        advance(&mut processor, &clock, Duration::from_secs(8192)).await;
+
+        assert!(wait_for(&mut log, "Scheduled").await.contains("fire #1"));
+        assert_eq!(
+            next_line(&mut log).await,
+            "[⚠️] Dropped fire #1 with an invalid time or burn duration"
+        );
+        assert!(actuator.stages().is_empty());
+        assert_eq!(processor.metrics().fires_executed, 0);
+        assert!(processor.next_deadline().is_none());
     }
 }
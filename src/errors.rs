@@ -11,4 +11,19 @@ pub enum FlightComputerError {
 
     #[error("Invalid command received: {0}")]
     InvalidCommand(#[from] serde_json::Error),
+
+    #[error("Failed to bind port {port}: {source}")]
+    BindFailed { port: u16, source: io::Error },
+
+    #[error("Invalid configuration: {0}")]
+    InvalidConfig(String),
+
+    #[error("Command and telemetry ports must differ, both are {0}")]
+    PortConflict(u16),
+
+    #[error("Telemetry client error: {0}")]
+    TelemetryClientError(#[source] io::Error),
+
+    #[error("Scheduling error: {0}")]
+    SchedulingError(String),
 }
@@ -1,23 +1,110 @@
-use crate::command::{Command, CommandProcessor};
+use crate::command::{Command, CommandOutcome, CommandProcessor};
 use crate::errors::FlightComputerError;
-use crate::telemetry::TelemetryHub;
-use std::{sync::Arc, time::Duration};
+use crate::telemetry::{TelemetryHub, TelemetryTags};
+use crate::{error, info, warn};
+use serde::{Deserialize, Serialize};
+use std::{
+    future::{self, Future},
+    io,
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    sync::Arc,
+    thread,
+    time::Duration,
+};
 
 use tokio::{
-    io::{AsyncBufReadExt, BufReader},
+    io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader, DuplexStream},
     net::TcpListener,
-    sync::Mutex,
-    time::interval,
+    runtime::Handle,
+    sync::{Mutex, Semaphore, oneshot},
+    task::{JoinHandle, JoinSet},
+    time::{Instant, MissedTickBehavior, interval_at, sleep_until},
 };
 
+/// The default port used for receiving commands.
+pub const DEFAULT_COMMAND_PORT: u16 = 8124;
+
+/// The default port used for telemetry logs.
+pub const DEFAULT_LOG_PORT: u16 = 8125;
+
+/// The default address both listeners bind to.
+pub const DEFAULT_BIND_ADDR: IpAddr = IpAddr::V4(Ipv4Addr::LOCALHOST);
+
+/// The default maximum number of simultaneous command connections.
+pub const DEFAULT_MAX_CONNECTIONS: usize = 16;
+
+/// The default upper bound on how long the tick loop sleeps between checks.
+pub const DEFAULT_TICK_INTERVAL: Duration = Duration::from_millis(10);
+
+/// The default interval between heartbeat telemetry messages.
+pub const DEFAULT_HEARTBEAT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The default number of commands per second each command connection may send.
+pub const DEFAULT_COMMAND_RATE_LIMIT: f64 = 20.0;
+
+/// The default time a command client has to authenticate once connected.
+pub const DEFAULT_AUTH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The default longest command line, in bytes, a client may send.
+pub const DEFAULT_MAX_LINE_LENGTH: usize = 64 * 1024;
+
+/// The number of bytes read from stdin that may wait to be handled.
+const STDIN_PIPE_CAPACITY: usize = 64 * 1024;
+
 /// Represents the FlightComputer, which unifies both commands and telemetry handling.
 pub struct FlightComputer {
+    /// The address the command and telemetry listeners bind to.
+    pub bind_addr: IpAddr,
     /// The port used for recieving commands.
     pub command_port: u16,
     /// The port used for telemetry logs.
     pub log_port: u16,
     /// The telemetry hub that manages telemetry data.
     pub telemetry_hub: TelemetryHub,
+    /// The tag the command processor sends with each kind of event, unless a
+    /// preconfigured `processor` is given, which keeps its own.
+    pub telemetry_tags: TelemetryTags,
+    /// The longest the tick loop sleeps before re-checking the schedule.
+    pub tick_interval: Duration,
+    /// The maximum number of simultaneous command connections; further
+    /// connections are refused with a "server busy" rejection.
+    pub max_connections: usize,
+    /// How often a heartbeat telemetry message is sent to show the link is alive.
+    pub heartbeat_interval: Duration,
+    /// How many commands per second each command connection may send; a
+    /// connection can burst up to this many at once, or one below a rate of
+    /// one per second, and commands beyond the budget are rejected as "rate
+    /// limited" without being processed. Each command in a batch counts.
+    pub command_rate_limit: f64,
+    /// A shared secret command clients must present before sending commands,
+    /// or `None` to accept commands from any client.
+    pub auth_token: Option<String>,
+    /// How long a command client has to authenticate when `auth_token` is
+    /// set, before the connection is closed and its slot freed.
+    pub auth_timeout: Duration,
+    /// Whether fires are only simulated, marked `[DRYRUN]` in telemetry and
+    /// never driving an actuator.
+    pub dry_run: bool,
+    /// A preconfigured command processor, e.g. with an actuator, delay bounds
+    /// or persistence, used instead of a default one. It should send its
+    /// telemetry on `telemetry_hub`; `dry_run` is applied to it, but
+    /// `telemetry_tags` is not.
+    pub processor: Option<CommandProcessor>,
+    /// Whether each command is echoed back to its sender, re-serialized in its
+    /// canonical form, on a line of its own just before the acknowledgement.
+    pub echo_commands: bool,
+    /// Whether newline-delimited commands are also read from stdin, and
+    /// acknowledged on stdout, alongside the command port.
+    pub read_stdin: bool,
+    /// The longest command line, in bytes, a client may send; a client
+    /// sending a longer one is rejected and disconnected.
+    pub max_line_length: usize,
+    /// An already bound listener to receive commands on, used instead of
+    /// binding `bind_addr` and `command_port`, e.g. under socket activation.
+    pub command_listener: Option<std::net::TcpListener>,
+    /// An already bound listener to serve telemetry on, used instead of
+    /// binding `bind_addr` and `log_port`.
+    pub log_listener: Option<std::net::TcpListener>,
 }
 
 impl FlightComputer {
@@ -30,79 +117,387 @@ impl FlightComputer {
     ///
     /// # Returns
     ///
-    /// A new `FlightComputer` instance.
+    /// A new `FlightComputer` instance bound to `DEFAULT_BIND_ADDR`, using
+    /// `DEFAULT_TICK_INTERVAL` and `DEFAULT_HEARTBEAT_INTERVAL`, and accepting
+    /// up to `DEFAULT_MAX_CONNECTIONS` command connections that may each send
+    /// `DEFAULT_COMMAND_RATE_LIMIT` commands per second and lines of up to
+    /// `DEFAULT_MAX_LINE_LENGTH` bytes without authentication or command echo,
+    /// and not reading commands from stdin.
     pub fn new(command_port: u16, log_port: u16) -> Self {
         Self {
+            bind_addr: DEFAULT_BIND_ADDR,
             command_port,
             log_port,
             telemetry_hub: TelemetryHub::new(),
+            telemetry_tags: TelemetryTags::default(),
+            tick_interval: DEFAULT_TICK_INTERVAL,
+            max_connections: DEFAULT_MAX_CONNECTIONS,
+            heartbeat_interval: DEFAULT_HEARTBEAT_INTERVAL,
+            command_rate_limit: DEFAULT_COMMAND_RATE_LIMIT,
+            auth_token: None,
+            auth_timeout: DEFAULT_AUTH_TIMEOUT,
+            dry_run: false,
+            processor: None,
+            echo_commands: false,
+            read_stdin: false,
+            max_line_length: DEFAULT_MAX_LINE_LENGTH,
+            command_listener: None,
+            log_listener: None,
         }
     }
 
-    /// Runs the main functionality of the `FlightComputer`.
+    /// Starts building a `FlightComputer`.
+    ///
+    /// # Returns
+    ///
+    /// A `FlightComputerBuilder` with every option at its default, listening
+    /// on `DEFAULT_COMMAND_PORT` and `DEFAULT_LOG_PORT`.
+    pub fn builder() -> FlightComputerBuilder {
+        FlightComputerBuilder::new()
+    }
+
+    /// Runs the main functionality of the `FlightComputer` until an error occurs.
     ///
-    /// This method spawns the telemetry service, starts the periodic tick loop,
-    /// and listens for incoming commands.
+    /// This is `run_with_shutdown` with a shutdown signal that never fires.
     ///
     /// # Returns
     ///
     /// A `Result` indicating success or a `FlightComputerError`.
     pub async fn run(self) -> Result<(), FlightComputerError> {
-        self.spawn_telemetry_server();
-        let processor = Arc::new(Mutex::new(CommandProcessor::new(
-            self.telemetry_hub.clone(),
-        )));
-        self.spawn_tick_loop(processor.clone());
-        self.listen_for_commands(processor).await
+        self.run_with_shutdown(future::pending()).await
+    }
+
+    /// Runs the main functionality of the `FlightComputer` until `shutdown` completes.
+    ///
+    /// This method sends a `🚦` boot telemetry message summarizing the
+    /// effective configuration as JSON, which every telemetry client receives
+    /// first, spawns the telemetry service and heartbeat, starts the
+    /// scheduling tick loop, and listens for incoming commands. Once `shutdown`
+    /// resolves, the accept loops and tick loop are stopped, every command
+    /// connection is aborted, the listeners are closed, and telemetry clients
+    /// are disconnected before returning.
+    ///
+    /// # Arguments
+    ///
+    /// * `shutdown` - A future that triggers a graceful shutdown when it completes.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` after a graceful shutdown, or a `FlightComputerError`, which is
+    /// `FlightComputerError::InvalidConfig` before anything is bound if the
+    /// tick or heartbeat interval is zero, or `FlightComputerError::PortConflict`
+    /// if both ports are the same and neither listener was supplied.
+    pub async fn run_with_shutdown(
+        mut self,
+        shutdown: impl Future<Output = ()>,
+    ) -> Result<(), FlightComputerError> {
+        self.check_intervals()?;
+        let (telemetry_listener, command_listener) = self.take_listeners().await?;
+        self.serve(telemetry_listener, command_listener, shutdown)
+            .await
+    }
+
+    /// Binds both listeners and runs the `FlightComputer` in a background task.
+    ///
+    /// Either port may be `0` to bind an ephemeral port, or a pre-bound
+    /// listener may be supplied instead; the handle reports the addresses
+    /// actually bound, so callers such as tests can connect to them.
+    ///
+    /// # Returns
+    ///
+    /// A `FlightComputerHandle` for the running computer, an `InvalidConfig`
+    /// error if the tick or heartbeat interval is zero, a
+    /// `FlightComputerError::PortConflict` if both ports are the same and
+    /// neither listener was supplied, or a `FlightComputerError::BindFailed`
+    /// if a listener could not be bound.
+    pub async fn start(mut self) -> Result<FlightComputerHandle, FlightComputerError> {
+        self.check_intervals()?;
+        let (telemetry_listener, command_listener) = self.take_listeners().await?;
+        let log_addr = telemetry_listener.local_addr()?;
+        let command_addr = command_listener.local_addr()?;
+
+        let (shutdown, shutdown_rx) = oneshot::channel();
+        let task = tokio::spawn(self.serve(telemetry_listener, command_listener, async {
+            let _ = shutdown_rx.await;
+        }));
+        Ok(FlightComputerHandle {
+            command_addr,
+            log_addr,
+            shutdown,
+            task,
+        })
+    }
+
+    /// Takes the supplied telemetry and command listeners, binding whichever
+    /// was not supplied.
+    ///
+    /// # Returns
+    ///
+    /// The telemetry and command listeners, or the `FlightComputerError` from
+    /// checking the ports or binding a listener.
+    async fn take_listeners(&mut self) -> Result<(TcpListener, TcpListener), FlightComputerError> {
+        if self.log_listener.is_none() && self.command_listener.is_none() {
+            self.check_ports()?;
+        }
+        let telemetry_listener =
+            listener_or_bind(self.log_listener.take(), self.bind_addr, self.log_port).await?;
+        let command_listener = listener_or_bind(
+            self.command_listener.take(),
+            self.bind_addr,
+            self.command_port,
+        )
+        .await?;
+        Ok((telemetry_listener, command_listener))
+    }
+
+    /// Checks that the command and telemetry ports differ, unless both are `0`
+    /// and so each get their own ephemeral port.
+    fn check_ports(&self) -> Result<(), FlightComputerError> {
+        if self.command_port == self.log_port && self.command_port != 0 {
+            return Err(FlightComputerError::PortConflict(self.command_port));
+        }
+        Ok(())
+    }
+
+    /// Serves commands and telemetry on already bound listeners until
+    /// `shutdown` completes, as described for `run_with_shutdown`.
+    ///
+    /// # Arguments
+    ///
+    /// * `telemetry_listener` - The listener bound to the telemetry port.
+    /// * `command_listener` - The listener bound to the command port.
+    /// * `shutdown` - A future that triggers a graceful shutdown when it completes.
+    async fn serve(
+        mut self,
+        telemetry_listener: TcpListener,
+        command_listener: TcpListener,
+        shutdown: impl Future<Output = ()>,
+    ) -> Result<(), FlightComputerError> {
+        let started = Instant::now();
+        let processor = self
+            .processor
+            .take()
+            .unwrap_or_else(|| {
+                CommandProcessor::new(self.telemetry_hub.clone())
+                    .with_tags(self.telemetry_tags.clone())
+            })
+            .with_dry_run(self.dry_run);
+        let boot = self.boot_config(
+            local_addr(&command_listener, (self.bind_addr, self.command_port)),
+            local_addr(&telemetry_listener, (self.bind_addr, self.log_port)),
+            &processor,
+        );
+        let boot = serde_json::to_string(&boot).expect("boot config serialization cannot fail");
+        self.telemetry_hub.send_boot_telemetry("🚦", &boot).await;
+        let mut background = JoinSet::new();
+        self.spawn_telemetry_server(&mut background, telemetry_listener);
+        self.spawn_heartbeat(&mut background, started);
+        let processor = Arc::new(Mutex::new(processor));
+        if self.read_stdin {
+            self.spawn_stdin_reader(&mut background, processor.clone());
+        }
+        let mut tick_loop = self.spawn_tick_loop(processor.clone());
+
+        let result = tokio::select! {
+            result = self.listen_for_commands(command_listener, processor) => result,
+            stopped = &mut tick_loop => Err(FlightComputerError::SchedulingError(match stopped {
+                Ok(()) => "tick loop exited".to_string(),
+                Err(e) => format!("tick loop failed: {}", e),
+            })),
+            _ = shutdown => Ok(()),
+        };
+
+        tick_loop.abort();
+        background.shutdown().await;
+        self.telemetry_hub.disconnect_all().await;
+        result
+    }
+
+    /// Checks that the tick and heartbeat intervals are non-zero, since a zero
+    /// tick interval would spin the tick loop and a zero heartbeat interval
+    /// cannot be timed.
+    fn check_intervals(&self) -> Result<(), FlightComputerError> {
+        if self.tick_interval.is_zero() {
+            return Err(FlightComputerError::InvalidConfig(
+                "tick interval must be non-zero".to_string(),
+            ));
+        }
+        if self.heartbeat_interval.is_zero() {
+            return Err(FlightComputerError::InvalidConfig(
+                "heartbeat interval must be non-zero".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Summarizes the effective configuration for the boot message.
+    ///
+    /// # Arguments
+    ///
+    /// * `command_addr` - The address the command listener is actually bound to.
+    /// * `log_addr` - The address the telemetry listener is actually bound to.
+    /// * `processor` - The processor that will handle commands.
+    fn boot_config(
+        &self,
+        command_addr: SocketAddr,
+        log_addr: SocketAddr,
+        processor: &CommandProcessor,
+    ) -> BootConfig {
+        let (min_delay_secs, max_delay_secs) = processor.delay_bounds();
+        BootConfig {
+            command_addr,
+            log_addr,
+            tick_interval_secs: self.tick_interval.as_secs_f64(),
+            heartbeat_interval_secs: self.heartbeat_interval.as_secs_f64(),
+            max_connections: self.max_connections,
+            command_rate_limit: self.command_rate_limit,
+            min_delay_secs,
+            max_delay_secs,
+            auth_required: self.auth_token.is_some(),
+            dry_run: self.dry_run,
+            echo_commands: self.echo_commands,
+            read_stdin: self.read_stdin,
+        }
     }
 
     /// Spawns the telemetry server to handle telemetry data.
     ///
-    /// This server listens for incoming connections and adds them
-    /// to the telemetry hub.
-    fn spawn_telemetry_server(&self) {
+    /// This server accepts connections on the telemetry listener and adds
+    /// them to the telemetry hub.
+    ///
+    /// # Arguments
+    ///
+    /// * `tasks` - The task set that owns the server, so it can be stopped on shutdown.
+    /// * `listener` - The listener bound to the telemetry port.
+    fn spawn_telemetry_server(&self, tasks: &mut JoinSet<()>, listener: TcpListener) {
         let telemetry_hub = self.telemetry_hub.clone();
-        let port = self.log_port;
-        tokio::spawn(async move {
-            let listener = TcpListener::bind(("127.0.0.1", port))
-                .await
-                .expect("Failed to bind telemetry port");
-            println!("Flight computer ready to telemeter data on port {}.", port);
+        info!("flight computer ready to telemeter data"; port = local_port(&listener, self.log_port));
+        tasks.spawn(async move {
             loop {
                 match listener.accept().await {
                     Ok((stream, addr)) => {
-                        println!("New telemetry client connected: {}", addr);
-                        telemetry_hub.add_client(stream).await;
+                        info!("telemetry client connected"; addr = addr);
+                        if let Err(e) = telemetry_hub.add_client(stream).await {
+                            warn!("telemetry client dropped"; addr = addr, error = e);
+                        }
                     }
-                    Err(e) => eprintln!("Telemetry listener error: {}", e),
+                    Err(e) => error!("telemetry listener failed"; error = e),
                 }
             }
         });
     }
 
-    /// Spawns the periodic tick loop for handling tasks.
+    /// Spawns the heartbeat that periodically reports the flight computer is alive.
     ///
-    /// This loop runs at a fixed interval and processes periodic tasks
-    /// using the `CommandProcessor`.
+    /// Every `heartbeat_interval` a `💓` telemetry message such as
+    /// `heartbeat seq=3 uptime=15.0s` is sent, with a sequence number that
+    /// starts at 1 and the uptime measured from `started`.
     ///
-    /// This definitely wastes CPU cycles, but is the simplest method to handle scheduling.
-    fn spawn_tick_loop(&self, processor: Arc<Mutex<CommandProcessor>>) {
+    /// # Arguments
+    ///
+    /// * `tasks` - The task set that owns the heartbeat, so it can be stopped on shutdown.
+    /// * `started` - The instant the flight computer started running.
+    fn spawn_heartbeat(&self, tasks: &mut JoinSet<()>, started: Instant) {
+        let telemetry_hub = self.telemetry_hub.clone();
+        let period = self.heartbeat_interval;
+        tasks.spawn(async move {
+            let mut heartbeats = interval_at(started + period, period);
+            heartbeats.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            for seq in 1u64.. {
+                heartbeats.tick().await;
+                let uptime = started.elapsed().as_secs_f64();
+                telemetry_hub
+                    .send_telemetry(
+                        "💓",
+                        &format!("heartbeat seq={} uptime={:.1}s", seq, uptime),
+                    )
+                    .await;
+            }
+        });
+    }
+
+    /// Spawns the reader that handles commands typed on stdin.
+    ///
+    /// Stdin is served like a command connection, sharing the processor with
+    /// the command port and acknowledging each line on stdout, but without
+    /// authentication. Reaching the end of stdin stops only the reader.
+    ///
+    /// # Arguments
+    ///
+    /// * `tasks` - The task set that owns the reader, so it can be stopped on shutdown.
+    /// * `processor` - A shared `CommandProcessor` instance for handling commands.
+    fn spawn_stdin_reader(&self, tasks: &mut JoinSet<()>, processor: Arc<Mutex<CommandProcessor>>) {
+        let rate_limit = TokenBucket::new(self.command_rate_limit);
+        let echo = self.echo_commands;
+        let max_line_length = self.max_line_length;
+        info!("flight computer reading commands from stdin");
+        tasks.spawn(async move {
+            let result = serve_command_client(
+                stdin_pipe(),
+                tokio::io::stdout(),
+                processor,
+                rate_limit,
+                None,
+                echo,
+                max_line_length,
+            )
+            .await;
+            if let Err(e) = result {
+                warn!("stdin command reader stopped"; error = e);
+            }
+        });
+    }
+
+    /// Spawns the tick loop that fires scheduled propulsion events.
+    ///
+    /// Rather than polling continuously, the loop sleeps until the processor's
+    /// next deadline, or at most `tick_interval`, and then calls
+    /// `CommandProcessor::tick`. Scheduling or cancelling a fire wakes the loop
+    /// so it re-arms against the new deadline, which drops the previous timer.
+    ///
+    /// # Arguments
+    ///
+    /// * `processor` - A shared `CommandProcessor` instance to tick.
+    ///
+    /// # Returns
+    ///
+    /// The handle of the spawned loop, which only completes if the loop panics.
+    fn spawn_tick_loop(&self, processor: Arc<Mutex<CommandProcessor>>) -> JoinHandle<()> {
+        let tick_interval = self.tick_interval;
         tokio::spawn(async move {
-            let mut interval = interval(Duration::from_micros(100));
+            let schedule_changed = processor.lock().await.schedule_changed();
             loop {
-                interval.tick().await;
-                processor.lock().await.tick().await;
+                let recheck = Instant::now() + tick_interval;
+                let wake_at = match processor.lock().await.next_deadline() {
+                    Some(deadline) => deadline.min(recheck),
+                    None => recheck,
+                };
+                tokio::select! {
+                    _ = sleep_until(wake_at) => processor.lock().await.tick().await,
+                    _ = schedule_changed.notified() => {}
+                }
             }
-        });
+        })
     }
 
     /// Listens for incoming commands from clients.
     ///
     /// This method accepts conections on the command port and processes
-    /// incoming commands using the `CommandProcessor`.
+    /// incoming commands using the `CommandProcessor`. Each connection runs in
+    /// a task owned by this method, so dropping the returned future aborts
+    /// every connection along with the listener. Once `max_connections`
+    /// clients are connected, new connections are sent a "server busy"
+    /// rejection and closed. Each connection is rate limited independently to
+    /// `command_rate_limit` commands per second. When `auth_token` is set,
+    /// clients must authenticate within `auth_timeout` before any of their
+    /// commands are processed.
+    /// A client sending a line longer than `max_line_length` is disconnected.
+    /// When `echo_commands` is set, each parsed command is echoed back before
+    /// its acknowledgement.
     ///
     /// # Arguments
     ///
+    /// * `listener` - The listener bound to the command port.
     /// * `processor` - A shared `CommandProcessor` instance for handling commands.
     ///
     /// # Returns
@@ -110,28 +505,1469 @@ impl FlightComputer {
     /// A `Result` indicating success or a `FlightComputerError`.
     async fn listen_for_commands(
         &self,
+        listener: TcpListener,
         processor: Arc<Mutex<CommandProcessor>>,
     ) -> Result<(), FlightComputerError> {
-        let listener = TcpListener::bind(("127.0.0.1", self.command_port)).await?;
-        println!(
-            "\nFlight computer ready for commands on port {}.",
-            self.command_port
-        );
+        info!("flight computer ready for commands"; port = local_port(&listener, self.command_port));
 
+        let slots = Arc::new(Semaphore::new(self.max_connections));
+        let mut connections = JoinSet::new();
         loop {
-            let (stream, addr) = listener.accept().await?;
-            println!("New command client connected: {}", addr);
+            let (mut stream, addr) = listener.accept().await?;
+            while connections.try_join_next().is_some() {}
+
+            let Ok(permit) = slots.clone().try_acquire_owned() else {
+                warn!("refused command client"; addr = addr, reason = "server busy");
+                let busy = CommandOutcome::Rejected {
+                    reason: "server busy".to_string(),
+                };
+                let _ = stream.write_all(&ack_line(&busy)).await;
+                let _ = stream.shutdown().await;
+                continue;
+            };
+            info!("command client connected"; addr = addr);
 
             let processor = processor.clone();
-            tokio::spawn(async move {
-                let mut lines = BufReader::new(stream).lines();
-                while let Ok(Some(line)) = lines.next_line().await {
-                    match serde_json::from_str::<Command>(&line) {
-                        Ok(cmd) => processor.lock().await.handle(cmd).await,
-                        Err(e) => eprintln!("Invalid command: {} ({})", line, e),
+            let telemetry = self.telemetry_hub.clone();
+            let rate_limit = TokenBucket::new(self.command_rate_limit);
+            let auth = self.auth_token.clone().map(|token| AuthPolicy {
+                token,
+                timeout: self.auth_timeout,
+            });
+            let echo = self.echo_commands;
+            let max_line_length = self.max_line_length;
+            connections.spawn(async move {
+                let (reader, writer) = stream.into_split();
+                let result = serve_command_client(
+                    reader,
+                    writer,
+                    processor,
+                    rate_limit,
+                    auth,
+                    echo,
+                    max_line_length,
+                )
+                .await;
+                report_disconnect(&telemetry, addr, result).await;
+                drop(permit);
+            });
+        }
+    }
+}
+
+/// A handle to a `FlightComputer` running in the background, returned by
+/// `FlightComputer::start`.
+pub struct FlightComputerHandle {
+    /// The address the command listener is bound to.
+    command_addr: SocketAddr,
+    /// The address the telemetry listener is bound to.
+    log_addr: SocketAddr,
+    /// Triggers the computer's graceful shutdown.
+    shutdown: oneshot::Sender<()>,
+    /// The task running the computer.
+    task: JoinHandle<Result<(), FlightComputerError>>,
+}
+
+impl FlightComputerHandle {
+    /// Returns the address the command listener is bound to.
+    pub fn command_addr(&self) -> SocketAddr {
+        self.command_addr
+    }
+
+    /// Returns the address the telemetry listener is bound to.
+    pub fn log_addr(&self) -> SocketAddr {
+        self.log_addr
+    }
+
+    /// Shuts the computer down gracefully and waits for it to stop.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` after a graceful shutdown, or the `FlightComputerError` that
+    /// stopped the computer earlier.
+    pub async fn shutdown(self) -> Result<(), FlightComputerError> {
+        let _ = self.shutdown.send(());
+        match self.task.await {
+            Ok(result) => result,
+            Err(e) => Err(FlightComputerError::Io(io::Error::other(format!(
+                "FlightComputer task failed: {}",
+                e
+            )))),
+        }
+    }
+}
+
+/// Builds a `FlightComputer`, defaulting every option that is not set.
+///
+/// Obtained from `FlightComputer::builder`, e.g.
+/// `FlightComputer::builder().command_port(9000).log_port(9001).build()`.
+pub struct FlightComputerBuilder {
+    /// The computer being configured.
+    computer: FlightComputer,
+}
+
+impl Default for FlightComputerBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FlightComputerBuilder {
+    /// Creates a builder with every option at its default.
+    pub fn new() -> Self {
+        Self {
+            computer: FlightComputer::new(DEFAULT_COMMAND_PORT, DEFAULT_LOG_PORT),
+        }
+    }
+
+    /// Sets the port used for receiving commands.
+    pub fn command_port(mut self, port: u16) -> Self {
+        self.computer.command_port = port;
+        self
+    }
+
+    /// Sets the port used for telemetry logs.
+    pub fn log_port(mut self, port: u16) -> Self {
+        self.computer.log_port = port;
+        self
+    }
+
+    /// Sets the address the command and telemetry listeners bind to.
+    pub fn bind_addr(mut self, addr: IpAddr) -> Self {
+        self.computer.bind_addr = addr;
+        self
+    }
+
+    /// Sets the telemetry hub that manages telemetry data.
+    pub fn telemetry_hub(mut self, telemetry_hub: TelemetryHub) -> Self {
+        self.computer.telemetry_hub = telemetry_hub;
+        self
+    }
+
+    /// Sets the tag the command processor sends with each kind of event.
+    pub fn telemetry_tags(mut self, tags: TelemetryTags) -> Self {
+        self.computer.telemetry_tags = tags;
+        self
+    }
+
+    /// Sets the longest the tick loop sleeps before re-checking the schedule.
+    pub fn tick_interval(mut self, interval: Duration) -> Self {
+        self.computer.tick_interval = interval;
+        self
+    }
+
+    /// Sets the maximum number of simultaneous command connections.
+    pub fn max_connections(mut self, max: usize) -> Self {
+        self.computer.max_connections = max;
+        self
+    }
+
+    /// Sets how often heartbeat telemetry is sent.
+    pub fn heartbeat_interval(mut self, interval: Duration) -> Self {
+        self.computer.heartbeat_interval = interval;
+        self
+    }
+
+    /// Sets how many commands per second each command connection may send.
+    pub fn command_rate_limit(mut self, per_second: f64) -> Self {
+        self.computer.command_rate_limit = per_second;
+        self
+    }
+
+    /// Requires command clients to authenticate with `token`.
+    pub fn auth_token(mut self, token: impl Into<String>) -> Self {
+        self.computer.auth_token = Some(token.into());
+        self
+    }
+
+    /// Sets how long a command client has to authenticate.
+    pub fn auth_timeout(mut self, timeout: Duration) -> Self {
+        self.computer.auth_timeout = timeout;
+        self
+    }
+
+    /// Enables or disables dry-run mode, in which fires are only simulated.
+    pub fn dry_run(mut self, enabled: bool) -> Self {
+        self.computer.dry_run = enabled;
+        self
+    }
+
+    /// Handles commands with a preconfigured processor instead of a default one.
+    ///
+    /// This is how an actuator, delay bounds or persistence is chosen, e.g.
+    /// `.processor(CommandProcessor::new(hub.clone()).with_actuator(gpio))`
+    /// for a computer built with `.telemetry_hub(hub)`. The computer's
+    /// dry-run setting is applied on top.
+    pub fn processor(mut self, processor: CommandProcessor) -> Self {
+        self.computer.processor = Some(processor);
+        self
+    }
+
+    /// Enables or disables echoing each parsed command back to its sender.
+    pub fn echo_commands(mut self, enabled: bool) -> Self {
+        self.computer.echo_commands = enabled;
+        self
+    }
+
+    /// Enables or disables reading commands from stdin as well as the command port.
+    pub fn read_stdin(mut self, enabled: bool) -> Self {
+        self.computer.read_stdin = enabled;
+        self
+    }
+
+    /// Receives commands on an already bound listener instead of binding one.
+    pub fn command_listener(mut self, listener: std::net::TcpListener) -> Self {
+        self.computer.command_listener = Some(listener);
+        self
+    }
+
+    /// Serves telemetry on an already bound listener instead of binding one.
+    pub fn log_listener(mut self, listener: std::net::TcpListener) -> Self {
+        self.computer.log_listener = Some(listener);
+        self
+    }
+
+    /// Sets the longest command line, in bytes, a client may send.
+    pub fn max_line_length(mut self, bytes: usize) -> Self {
+        self.computer.max_line_length = bytes;
+        self
+    }
+
+    /// Finishes building the `FlightComputer`.
+    pub fn build(self) -> FlightComputer {
+        self.computer
+    }
+}
+
+/// Returns the address a listener is bound to, or `configured` if it cannot be read.
+fn local_addr(listener: &TcpListener, configured: impl Into<SocketAddr>) -> SocketAddr {
+    listener.local_addr().unwrap_or_else(|_| configured.into())
+}
+
+/// Returns the port a listener is bound to, or `configured` if it cannot be read.
+fn local_port(listener: &TcpListener, configured: u16) -> u16 {
+    listener.local_addr().map_or(configured, |addr| addr.port())
+}
+
+/// Adopts a pre-bound listener into the runtime, or binds one if none was supplied.
+///
+/// # Arguments
+///
+/// * `listener` - The pre-bound listener, if any.
+/// * `addr` - The address to bind to otherwise.
+/// * `port` - The port to bind to otherwise.
+async fn listener_or_bind(
+    listener: Option<std::net::TcpListener>,
+    addr: IpAddr,
+    port: u16,
+) -> Result<TcpListener, FlightComputerError> {
+    match listener {
+        Some(listener) => {
+            listener.set_nonblocking(true)?;
+            Ok(TcpListener::from_std(listener)?)
+        }
+        None => bind_listener(addr, port).await,
+    }
+}
+
+/// Spawns a thread that copies stdin, line by line, into an in-memory pipe.
+///
+/// A read blocked on `tokio::io::stdin` keeps the runtime from shutting down
+/// until a line arrives, whereas a plain thread is simply abandoned on exit.
+///
+/// # Returns
+///
+/// The end of the pipe that stdin's lines can be read from.
+fn stdin_pipe() -> DuplexStream {
+    let (reader, mut writer) = tokio::io::duplex(STDIN_PIPE_CAPACITY);
+    let runtime = Handle::current();
+    thread::spawn(move || {
+        for line in io::stdin().lines() {
+            let Ok(mut line) = line else {
+                break;
+            };
+            line.push('\n');
+            if runtime.block_on(writer.write_all(line.as_bytes())).is_err() {
+                break;
+            }
+        }
+    });
+    reader
+}
+
+/// Binds a listener, reporting a failure as `FlightComputerError::BindFailed`.
+///
+/// # Arguments
+///
+/// * `addr` - The address to bind to.
+/// * `port` - The port to bind to.
+async fn bind_listener(addr: IpAddr, port: u16) -> Result<TcpListener, FlightComputerError> {
+    TcpListener::bind((addr, port))
+        .await
+        .map_err(|source| FlightComputerError::BindFailed { port, source })
+}
+
+/// Reads newline-delimited commands from a single command client until it disconnects.
+///
+/// Every line is acknowledged on the client's writer with a one-line JSON
+/// `CommandOutcome`. A line holding a JSON array is a batch: its commands are
+/// handled in order under a single lock, so no other connection's commands
+/// are interleaved, and it is acknowledged with an array of outcomes. Lines
+/// that exceed the connection's rate limit, with each command in a batch
+/// counting against it, are rejected without being processed. Lines that do
+/// not parse are rejected through `CommandProcessor::invalid`, so they are
+/// counted and reported like any other invalid command.
+///
+/// When `auth` is set, the first line must be `{"auth":"<token>"}` and arrive
+/// within the policy's timeout; it is acknowledged as accepted, and any other
+/// first line, or none in time, is rejected and the connection closed before
+/// a command is processed.
+///
+/// When `echo` is set, each line that parses is first echoed back as
+/// `{"echo":<command>}` in canonical form, so a legacy `2.0` is echoed as
+/// `{"echo":{"type":"schedule","delay":2.0}}`; a batch is echoed as an array
+/// with `null` in place of each element that failed to parse.
+///
+/// Lines are read into a buffer of at most `max_line_length` bytes. A longer
+/// line is rejected as invalid and the connection closed, without buffering
+/// the rest of it.
+///
+/// # Arguments
+///
+/// * `reader` - The side of the command client's connection that commands arrive on.
+/// * `writer` - The side of the connection acknowledgements are written to.
+/// * `processor` - A shared `CommandProcessor` instance for handling commands.
+/// * `rate_limit` - The token bucket limiting this connection's command rate.
+/// * `auth` - The token the client must authenticate with and how long it
+///   has to, if authentication is required.
+/// * `echo` - Whether to echo each parsed command before acknowledging it.
+/// * `max_line_length` - The longest line, in bytes, the client may send.
+///
+/// # Returns
+///
+/// `Ok(())` when the client closes the connection, or the I/O error that ended
+/// it, which is `io::ErrorKind::PermissionDenied` if authentication failed and
+/// `io::ErrorKind::TimedOut` if it was not attempted in time, and
+/// `io::ErrorKind::InvalidData` if a line was too long.
+async fn serve_command_client(
+    reader: impl AsyncRead + Unpin,
+    mut writer: impl AsyncWrite + Unpin,
+    processor: Arc<Mutex<CommandProcessor>>,
+    mut rate_limit: TokenBucket,
+    auth: Option<AuthPolicy>,
+    echo: bool,
+    max_line_length: usize,
+) -> io::Result<()> {
+    let mut lines = LineReader::new(reader, max_line_length);
+    if let Some(AuthPolicy { token, timeout }) = auth {
+        let Ok(first) = tokio::time::timeout(timeout, lines.next_line()).await else {
+            let expired = CommandOutcome::Rejected {
+                reason: "authentication timed out".to_string(),
+            };
+            writer.write_all(&ack_line(&expired)).await?;
+            return Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                "authentication timed out",
+            ));
+        };
+        let line = match first? {
+            ReadLine::Line(line) => line,
+            ReadLine::TooLong => {
+                return reject_long_line(&mut writer, &processor, max_line_length).await;
+            }
+            ReadLine::Closed => return Ok(()),
+        };
+        let authenticated = serde_json::from_str::<Authentication>(&line)
+            .is_ok_and(|presented| constant_time_eq(presented.auth.as_bytes(), token.as_bytes()));
+        if !authenticated {
+            let refused = CommandOutcome::Rejected {
+                reason: "authentication failed".to_string(),
+            };
+            writer.write_all(&ack_line(&refused)).await?;
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                "authentication failed",
+            ));
+        }
+        writer
+            .write_all(&ack_line(&CommandOutcome::accepted()))
+            .await?;
+        writer.flush().await?;
+    }
+    loop {
+        let line = match lines.next_line().await? {
+            ReadLine::Line(line) => line,
+            ReadLine::TooLong => {
+                return reject_long_line(&mut writer, &processor, max_line_length).await;
+            }
+            ReadLine::Closed => return Ok(()),
+        };
+        let batch = line
+            .trim_start()
+            .starts_with('[')
+            .then(|| parse_batch(&line));
+        let cost = match &batch {
+            Some(Ok(commands)) => commands.len().max(1),
+            _ => 1,
+        };
+        if !rate_limit.try_take(cost) {
+            let limited = CommandOutcome::Rejected {
+                reason: "rate limited".to_string(),
+            };
+            writer.write_all(&ack_line(&limited)).await?;
+            writer.flush().await?;
+            continue;
+        }
+        let ack = if let Some(batch) = batch {
+            match batch {
+                Ok(commands) => {
+                    if echo {
+                        let parsed: Vec<_> = commands.iter().map(|cmd| cmd.as_ref().ok()).collect();
+                        writer.write_all(&echo_line(&parsed)).await?;
                     }
+                    ack_line(&handle_batch(commands, &processor).await)
                 }
-            });
+                Err(e) => {
+                    warn!("invalid command batch"; command = line, error = e);
+                    let reason = format!("invalid command batch: {}", e);
+                    ack_line(&[processor.lock().await.invalid(reason).await])
+                }
+            }
+        } else {
+            let outcome = match serde_json::from_str::<Command>(&line) {
+                Ok(cmd) => {
+                    if echo {
+                        writer.write_all(&echo_line(&cmd)).await?;
+                    }
+                    processor.lock().await.handle(cmd).await
+                }
+                Err(e) => {
+                    warn!("invalid command"; command = line, error = e);
+                    let reason = format!("invalid command: {}", e);
+                    processor.lock().await.invalid(reason).await
+                }
+            };
+            ack_line(&outcome)
+        };
+        writer.write_all(&ack).await?;
+        writer.flush().await?;
+    }
+}
+
+/// Rejects a command line that exceeded `max_line_length` as invalid and
+/// acknowledges the rejection before the connection is closed.
+///
+/// # Returns
+///
+/// The `io::ErrorKind::InvalidData` error that ends the connection, or the
+/// error from writing the acknowledgement.
+async fn reject_long_line(
+    writer: &mut (impl AsyncWrite + Unpin),
+    processor: &Mutex<CommandProcessor>,
+    max_line_length: usize,
+) -> io::Result<()> {
+    let reason = format!(
+        "Command line exceeds {} bytes, closing connection",
+        max_line_length
+    );
+    let outcome = processor.lock().await.invalid(reason.clone()).await;
+    writer.write_all(&ack_line(&outcome)).await?;
+    writer.flush().await?;
+    Err(io::Error::new(io::ErrorKind::InvalidData, reason))
+}
+
+/// Parses a line holding a JSON array of commands.
+///
+/// # Arguments
+///
+/// * `line` - The line holding the batch.
+///
+/// # Returns
+///
+/// Each element parsed as a command, or the error if the line is not a JSON array.
+fn parse_batch(line: &str) -> serde_json::Result<Vec<serde_json::Result<Command>>> {
+    let elements = serde_json::from_str::<Vec<serde_json::Value>>(line)?;
+    Ok(elements.into_iter().map(serde_json::from_value).collect())
+}
+
+/// Handles a batch of commands in order while holding the processor lock.
+///
+/// An element that is not a valid command is rejected as invalid without
+/// affecting the rest of the batch.
+///
+/// # Arguments
+///
+/// * `commands` - The batch's elements, as parsed by `parse_batch`.
+/// * `processor` - A shared `CommandProcessor` instance for handling commands.
+///
+/// # Returns
+///
+/// The outcome of each command in the batch.
+async fn handle_batch(
+    commands: Vec<serde_json::Result<Command>>,
+    processor: &Mutex<CommandProcessor>,
+) -> Vec<CommandOutcome> {
+    let mut processor = processor.lock().await;
+    let mut outcomes = Vec::with_capacity(commands.len());
+    for command in commands {
+        let outcome = match command {
+            Ok(cmd) => processor.handle(cmd).await,
+            Err(e) => processor.invalid(format!("invalid command: {}", e)).await,
+        };
+        outcomes.push(outcome);
+    }
+    outcomes
+}
+
+/// Logs and telemeters that a command client disconnected.
+///
+/// # Arguments
+///
+/// * `telemetry` - The telemetry hub to report the disconnect on.
+/// * `addr` - The address of the client.
+/// * `result` - How the client's connection ended.
+async fn report_disconnect(telemetry: &TelemetryHub, addr: SocketAddr, result: io::Result<()>) {
+    let msg = match result {
+        Ok(()) => {
+            info!("command client disconnected"; addr = addr);
+            format!("Command client disconnected: {}", addr)
         }
+        Err(e) => {
+            info!("command client disconnected"; addr = addr, error = e);
+            format!("Command client disconnected: {} (error: {})", addr, e)
+        }
+    };
+    telemetry.send_telemetry("🔌", &msg).await;
+}
+
+/// What a command client must do to authenticate before sending commands.
+struct AuthPolicy {
+    /// The shared secret the client must present.
+    token: String,
+    /// How long the client has to present it.
+    timeout: Duration,
+}
+
+/// Compares a presented secret with the expected one in time that depends
+/// only on their lengths, so a mismatch does not reveal where the two differ.
+fn constant_time_eq(presented: &[u8], expected: &[u8]) -> bool {
+    presented.len() == expected.len()
+        && presented
+            .iter()
+            .zip(expected)
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+/// The effective configuration, sent as the JSON payload of the `🚦` boot
+/// telemetry message that every telemetry client receives first.
+#[derive(Serialize)]
+struct BootConfig {
+    /// The address commands are received on.
+    command_addr: SocketAddr,
+    /// The address telemetry is served on.
+    log_addr: SocketAddr,
+    /// The longest the tick loop sleeps before re-checking the schedule.
+    tick_interval_secs: f64,
+    /// How often heartbeat telemetry is sent.
+    heartbeat_interval_secs: f64,
+    /// The maximum number of simultaneous command connections.
+    max_connections: usize,
+    /// How many commands per second each command connection may send.
+    command_rate_limit: f64,
+    /// The shortest accepted fire delay.
+    min_delay_secs: f64,
+    /// The longest accepted fire delay.
+    max_delay_secs: f64,
+    /// Whether command clients must authenticate, without revealing the token.
+    auth_required: bool,
+    /// Whether fires are only simulated.
+    dry_run: bool,
+    /// Whether commands are echoed back to their sender.
+    echo_commands: bool,
+    /// Whether commands are also read from stdin.
+    read_stdin: bool,
+}
+
+/// The first line a command client sends when the command port requires a token.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Authentication {
+    /// The shared secret presented by the client.
+    auth: String,
+}
+
+/// A line read by `LineReader::next_line`.
+enum ReadLine {
+    /// A complete line, without its line ending.
+    Line(String),
+    /// A line longer than the reader's limit, of which only up to the limit was read.
+    TooLong,
+    /// The end of the input.
+    Closed,
+}
+
+/// Reads newline-terminated lines, buffering no more than a fixed number of
+/// bytes of any one line.
+struct LineReader<R> {
+    /// The buffered input.
+    reader: BufReader<R>,
+    /// The longest line, in bytes, that is read in full.
+    max_len: usize,
+}
+
+impl<R: AsyncRead + Unpin> LineReader<R> {
+    /// Creates a reader of lines of up to `max_len` bytes.
+    fn new(reader: R, max_len: usize) -> Self {
+        Self {
+            reader: BufReader::new(reader),
+            max_len,
+        }
+    }
+
+    /// Reads the next line, stopping as soon as it exceeds the length limit.
+    ///
+    /// A final line without a newline is still returned, and `\r\n` line
+    /// endings are accepted.
+    ///
+    /// # Returns
+    ///
+    /// The line, `ReadLine::TooLong` or `ReadLine::Closed`, or an I/O error,
+    /// which is `io::ErrorKind::InvalidData` if the line is not UTF-8.
+    async fn next_line(&mut self) -> io::Result<ReadLine> {
+        let mut line = Vec::new();
+        loop {
+            let available = self.reader.fill_buf().await?;
+            if available.is_empty() {
+                if line.is_empty() {
+                    return Ok(ReadLine::Closed);
+                }
+                break;
+            }
+            let (taken, complete) = match available.iter().position(|&b| b == b'\n') {
+                Some(newline) => (newline, true),
+                None => (available.len(), false),
+            };
+            line.extend_from_slice(&available[..taken]);
+            self.reader.consume(taken + usize::from(complete));
+            if line.len() > self.max_len {
+                return Ok(ReadLine::TooLong);
+            }
+            if complete {
+                break;
+            }
+        }
+        if line.last() == Some(&b'\r') {
+            line.pop();
+        }
+        String::from_utf8(line)
+            .map(ReadLine::Line)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+/// A token bucket that limits how many commands a connection may send per second.
+struct TokenBucket {
+    /// The refill rate in tokens per second.
+    rate: f64,
+    /// The most tokens the bucket holds, the rate but at least one, so that a
+    /// rate below one command per second still lets commands through.
+    capacity: f64,
+    /// The tokens currently available.
+    tokens: f64,
+    /// When `tokens` was last refilled.
+    refilled: Instant,
+}
+
+impl TokenBucket {
+    /// Creates a full bucket that refills at `rate` tokens per second.
+    fn new(rate: f64) -> Self {
+        let capacity = rate.max(1.0);
+        Self {
+            rate,
+            capacity,
+            tokens: capacity,
+            refilled: Instant::now(),
+        }
+    }
+
+    /// Takes `count` tokens, returning `false` without taking any if the
+    /// bucket holds fewer.
+    fn try_take(&mut self, count: usize) -> bool {
+        let now = Instant::now();
+        let earned = now.duration_since(self.refilled).as_secs_f64() * self.rate;
+        self.tokens = (self.tokens + earned).min(self.capacity);
+        self.refilled = now;
+        let cost = count as f64;
+        if self.tokens >= cost {
+            self.tokens -= cost;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// The line echoing a parsed command, or a batch of them, back to its sender.
+#[derive(Serialize)]
+struct Echo<'a, T: ?Sized> {
+    /// The command in canonical form.
+    echo: &'a T,
+}
+
+/// Serializes a command, or a batch of them, as a newline-terminated JSON echo.
+fn echo_line<T: Serialize + ?Sized>(command: &T) -> Vec<u8> {
+    ack_line(&Echo { echo: command })
+}
+
+/// Serializes a `CommandOutcome`, or a batch of them, as a newline-terminated
+/// JSON acknowledgement.
+fn ack_line<T: Serialize + ?Sized>(outcome: &T) -> Vec<u8> {
+    let mut line = serde_json::to_vec(outcome).expect("outcome serialization cannot fail");
+    line.push(b'\n');
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::tests::RecordingActuator;
+    use crate::telemetry::tests::{Received, connect, next_line, wait_for};
+    use std::time::Duration;
+    use tokio::net::TcpStream;
+    use tokio::time::Instant;
+
+    /// Returns a port that was free when this was called.
+    fn free_port() -> u16 {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        listener.local_addr().unwrap().port()
+    }
+
+    /// Connects a command client to `addr`, returning its acknowledgement lines.
+    async fn commands(addr: (IpAddr, u16)) -> Received {
+        BufReader::new(connect_when_ready(addr).await).lines()
+    }
+
+    /// Sends a command line and returns its acknowledgement.
+    async fn send(client: &mut Received, line: &str) -> String {
+        let stream = client.get_mut().get_mut();
+        stream
+            .write_all(format!("{}\n", line).as_bytes())
+            .await
+            .unwrap();
+        next_line(client).await
+    }
+
+    /// Connects to `addr`, retrying while the listener starts up.
+    async fn connect_when_ready(addr: (IpAddr, u16)) -> TcpStream {
+        for _ in 0..100 {
+            if let Ok(stream) = TcpStream::connect(addr).await {
+                return stream;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("nothing listening on {:?}", addr);
+    }
+
+    #[tokio::test]
+    async fn fire_ignites_on_its_deadline() {
+        let computer = FlightComputer::new(0, 0);
+        let mut log = connect(&computer.telemetry_hub).await;
+        let processor = Arc::new(Mutex::new(CommandProcessor::new(
+            computer.telemetry_hub.clone(),
+        )));
+        computer.spawn_tick_loop(processor.clone());
+
+        let scheduled = Instant::now();
+        let command = serde_json::from_str("0.2").unwrap();
+        processor.lock().await.handle(command).await;
+        assert!(
+            next_line(&mut log)
+                .await
+                .ends_with("Scheduled 'main' fire #1 in 0.20s")
+        );
+        assert!(
+            next_line(&mut log)
+                .await
+                .contains("Firing propulsion 'main' now! (priority 0, +")
+        );
+
+        let elapsed = scheduled.elapsed();
+        assert!(elapsed >= Duration::from_millis(200));
+        assert!(
+            elapsed < Duration::from_millis(250),
+            "fired late: {:?}",
+            elapsed
+        );
+    }
+
+    #[tokio::test]
+    async fn coarse_tick_interval_still_fires_on_time() {
+        let computer = FlightComputer {
+            tick_interval: Duration::from_secs(1),
+            ..FlightComputer::new(0, 0)
+        };
+        let mut log = connect(&computer.telemetry_hub).await;
+        let processor = Arc::new(Mutex::new(CommandProcessor::new(
+            computer.telemetry_hub.clone(),
+        )));
+        computer.spawn_tick_loop(processor.clone());
+
+        let scheduled = Instant::now();
+        let command = serde_json::from_str("0.1").unwrap();
+        processor.lock().await.handle(command).await;
+        next_line(&mut log).await;
+        assert!(
+            next_line(&mut log)
+                .await
+                .contains("Firing propulsion 'main' now! (priority 0, +")
+        );
+
+        // Scheduling wakes the loop, so the interval only bounds its rechecks.
+        let elapsed = scheduled.elapsed();
+        assert!(elapsed >= Duration::from_millis(100));
+        assert!(
+            elapsed < Duration::from_millis(150),
+            "fired late: {:?}",
+            elapsed
+        );
+    }
+
+    #[tokio::test]
+    async fn wildcard_bind_accepts_non_loopback_clients() {
+        // Connecting a UDP socket picks the outbound interface without sending anything.
+        let probe = std::net::UdpSocket::bind("0.0.0.0:0").unwrap();
+        let Ok(local_ip) = probe
+            .connect("192.0.2.1:9")
+            .and_then(|()| probe.local_addr())
+            .map(|addr| addr.ip())
+        else {
+            eprintln!("no non-loopback interface, skipping");
+            return;
+        };
+        let computer = FlightComputer {
+            bind_addr: IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+            ..FlightComputer::new(free_port(), free_port())
+        };
+        let command_port = computer.command_port;
+        let mut log = connect(&computer.telemetry_hub).await;
+        tokio::spawn(computer.run());
+
+        let mut stream = connect_when_ready((local_ip, command_port)).await;
+        assert!(next_line(&mut log).await.contains("[🚦]"));
+        stream.write_all(b"5.0\n").await.unwrap();
+
+        assert!(
+            next_line(&mut log)
+                .await
+                .ends_with("Scheduled 'main' fire #1 in 5.00s")
+        );
+    }
+
+    #[tokio::test]
+    async fn shutdown_is_prompt_and_frees_the_ports() {
+        let computer = FlightComputer::new(free_port(), free_port());
+        let command_addr = (computer.bind_addr, computer.command_port);
+        let log_addr = (computer.bind_addr, computer.log_port);
+        let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
+        let running = tokio::spawn(computer.run_with_shutdown(async {
+            let _ = shutdown_rx.await;
+        }));
+        let mut log = BufReader::new(connect_when_ready(log_addr).await).lines();
+        let _client = connect_when_ready(command_addr).await;
+        assert!(next_line(&mut log).await.contains("[🚦]"));
+
+        shutdown_tx.send(()).unwrap();
+        tokio::time::timeout(Duration::from_secs(1), running)
+            .await
+            .expect("shutdown did not complete promptly")
+            .unwrap()
+            .unwrap();
+
+        assert!(log.next_line().await.unwrap().is_none());
+        TcpListener::bind(command_addr).await.unwrap();
+        TcpListener::bind(log_addr).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn commands_are_acknowledged_on_their_connection() {
+        let computer = FlightComputer::new(free_port(), free_port());
+        let command_addr = (computer.bind_addr, computer.command_port);
+        tokio::spawn(computer.run());
+        let mut client = commands(command_addr).await;
+
+        let accepted = send(&mut client, r#"{"type":"schedule","delay":60.0}"#).await;
+        let rejected = send(&mut client, r#"{"type":"schedule","delay":-5.0}"#).await;
+        let unparsed = send(&mut client, "launch").await;
+
+        assert_eq!(accepted, r#"{"status":"accepted","id":1}"#);
+        assert_eq!(
+            rejected,
+            r#"{"status":"rejected","reason":"Invalid delay value: -5"}"#
+        );
+        assert!(unparsed.starts_with(r#"{"status":"rejected","reason":"invalid command: "#));
+    }
+
+    #[tokio::test]
+    async fn taken_port_is_reported_as_bind_failed() {
+        let taken = TcpListener::bind((DEFAULT_BIND_ADDR, 0)).await.unwrap();
+        let port = taken.local_addr().unwrap().port();
+
+        let result = FlightComputer::new(port, free_port()).run().await;
+
+        match result {
+            Err(FlightComputerError::BindFailed { port: failed, .. }) => assert_eq!(failed, port),
+            Err(e) => panic!("expected BindFailed, got {}", e),
+            Ok(()) => panic!("bound a port that was already taken"),
+        }
+    }
+
+    #[tokio::test]
+    async fn connections_beyond_the_limit_are_refused() {
+        let computer = FlightComputer {
+            max_connections: 2,
+            ..FlightComputer::new(free_port(), free_port())
+        };
+        let command_addr = (computer.bind_addr, computer.command_port);
+        tokio::spawn(computer.run());
+        let status = r#"{"type":"status"}"#;
+        let accepted = r#"{"status":"accepted"}"#;
+        let mut first = commands(command_addr).await;
+        let mut second = commands(command_addr).await;
+        assert_eq!(send(&mut first, status).await, accepted);
+        assert_eq!(send(&mut second, status).await, accepted);
+
+        let mut extra = commands(command_addr).await;
+        assert_eq!(
+            next_line(&mut extra).await,
+            r#"{"status":"rejected","reason":"server busy"}"#
+        );
+        assert!(extra.next_line().await.unwrap().is_none());
+
+        assert_eq!(send(&mut first, status).await, accepted);
+        assert_eq!(send(&mut second, status).await, accepted);
+    }
+
+    #[tokio::test]
+    async fn closing_a_command_client_is_telemetered() {
+        let computer = FlightComputer::new(free_port(), free_port());
+        let command_addr = (computer.bind_addr, computer.command_port);
+        let mut log = connect(&computer.telemetry_hub).await;
+        tokio::spawn(computer.run());
+        let client = connect_when_ready(command_addr).await;
+        let addr = client.local_addr().unwrap();
+
+        drop(client);
+
+        let disconnected = wait_for(&mut log, "Command client disconnected").await;
+        assert!(
+            disconnected.ends_with(&format!("[🔌] Command client disconnected: {}", addr)),
+            "{}",
+            disconnected
+        );
+    }
+
+    #[tokio::test]
+    async fn heartbeats_arrive_on_their_interval() {
+        let computer = FlightComputer {
+            heartbeat_interval: Duration::from_millis(50),
+            ..FlightComputer::new(free_port(), free_port())
+        };
+        let mut log = connect(&computer.telemetry_hub).await;
+        tokio::spawn(computer.run());
+
+        let first = wait_for(&mut log, "[💓]").await;
+        assert!(first.contains("[💓] heartbeat seq=1 uptime="), "{}", first);
+        let second = wait_for(&mut log, "[💓]").await;
+        assert!(
+            second.contains("[💓] heartbeat seq=2 uptime="),
+            "{}",
+            second
+        );
+    }
+
+    #[tokio::test]
+    async fn zero_intervals_are_rejected() {
+        let zero_tick = FlightComputer {
+            tick_interval: Duration::ZERO,
+            ..FlightComputer::new(free_port(), free_port())
+        };
+        let zero_heartbeat = FlightComputer {
+            heartbeat_interval: Duration::ZERO,
+            ..FlightComputer::new(free_port(), free_port())
+        };
+
+        for (computer, reason) in [
+            (zero_tick, "tick interval must be non-zero"),
+            (zero_heartbeat, "heartbeat interval must be non-zero"),
+        ] {
+            match computer.run().await {
+                Err(FlightComputerError::InvalidConfig(got)) => assert_eq!(got, reason),
+                Err(e) => panic!("expected InvalidConfig, got {}", e),
+                Ok(()) => panic!("ran with a zero interval"),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn commands_beyond_the_rate_limit_are_rejected() {
+        let computer = FlightComputer {
+            command_rate_limit: 3.0,
+            ..FlightComputer::new(free_port(), free_port())
+        };
+        let command_addr = (computer.bind_addr, computer.command_port);
+        tokio::spawn(computer.run());
+        let mut client = commands(command_addr).await;
+
+        let mut acks = Vec::new();
+        for _ in 0..5 {
+            acks.push(send(&mut client, r#"{"type":"status"}"#).await);
+        }
+
+        let limited = r#"{"status":"rejected","reason":"rate limited"}"#;
+        assert_eq!(acks[..3], [r#"{"status":"accepted"}"#; 3]);
+        assert_eq!(acks[3..], [limited; 2]);
+    }
+
+    #[tokio::test]
+    async fn rate_below_one_still_admits_a_command() {
+        let computer = FlightComputer {
+            command_rate_limit: 0.5,
+            ..FlightComputer::new(free_port(), free_port())
+        };
+        let command_addr = (computer.bind_addr, computer.command_port);
+        tokio::spawn(computer.run());
+        let mut client = commands(command_addr).await;
+
+        let first = send(&mut client, r#"{"type":"status"}"#).await;
+        let second = send(&mut client, r#"{"type":"status"}"#).await;
+
+        assert_eq!(first, r#"{"status":"accepted"}"#);
+        assert_eq!(second, r#"{"status":"rejected","reason":"rate limited"}"#);
+    }
+
+    #[tokio::test]
+    async fn correct_token_unlocks_commands() {
+        let computer = FlightComputer {
+            auth_token: Some("s3cret".to_string()),
+            ..FlightComputer::new(free_port(), free_port())
+        };
+        let command_addr = (computer.bind_addr, computer.command_port);
+        tokio::spawn(computer.run());
+        let mut client = commands(command_addr).await;
+
+        let auth = send(&mut client, r#"{"auth":"s3cret"}"#).await;
+        let ack = send(&mut client, r#"{"type":"schedule","delay":60.0}"#).await;
+
+        assert_eq!(auth, r#"{"status":"accepted"}"#);
+        assert_eq!(ack, r#"{"status":"accepted","id":1}"#);
+    }
+
+    #[tokio::test]
+    async fn wrong_token_is_refused_and_closed() {
+        let computer = FlightComputer {
+            auth_token: Some("s3cret".to_string()),
+            ..FlightComputer::new(free_port(), free_port())
+        };
+        let command_addr = (computer.bind_addr, computer.command_port);
+        tokio::spawn(computer.run());
+        let mut client = commands(command_addr).await;
+
+        let refused = send(&mut client, r#"{"auth":"guess"}"#).await;
+
+        assert_eq!(
+            refused,
+            r#"{"status":"rejected","reason":"authentication failed"}"#
+        );
+        assert!(client.next_line().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn silent_client_times_out_authenticating() {
+        let computer = FlightComputer {
+            auth_token: Some("s3cret".to_string()),
+            auth_timeout: Duration::from_millis(50),
+            ..FlightComputer::new(free_port(), free_port())
+        };
+        let command_addr = (computer.bind_addr, computer.command_port);
+        tokio::spawn(computer.run());
+        let mut client = commands(command_addr).await;
+
+        assert_eq!(
+            next_line(&mut client).await,
+            r#"{"status":"rejected","reason":"authentication timed out"}"#
+        );
+        assert!(client.next_line().await.unwrap().is_none());
+    }
+
+    #[test]
+    fn constant_time_eq_compares_whole_secrets() {
+        assert!(constant_time_eq(b"s3cret", b"s3cret"));
+        assert!(!constant_time_eq(b"s3creT", b"s3cret"));
+        assert!(!constant_time_eq(b"s3cre", b"s3cret"));
+        assert!(!constant_time_eq(b"", b"s3cret"));
+    }
+
+    #[test]
+    fn builder_defaults_unset_options() {
+        let computer = FlightComputer::builder()
+            .command_port(9000)
+            .tick_interval(Duration::from_millis(50))
+            .build();
+
+        assert_eq!(computer.command_port, 9000);
+        assert_eq!(computer.tick_interval, Duration::from_millis(50));
+        assert_eq!(computer.log_port, DEFAULT_LOG_PORT);
+        assert_eq!(computer.bind_addr, DEFAULT_BIND_ADDR);
+        assert_eq!(computer.max_connections, DEFAULT_MAX_CONNECTIONS);
+        assert_eq!(computer.heartbeat_interval, DEFAULT_HEARTBEAT_INTERVAL);
+        assert_eq!(computer.command_rate_limit, DEFAULT_COMMAND_RATE_LIMIT);
+        assert_eq!(computer.auth_token, None);
+        assert_eq!(computer.auth_timeout, DEFAULT_AUTH_TIMEOUT);
+        assert!(computer.processor.is_none());
+    }
+
+    #[tokio::test]
+    async fn each_command_in_a_batch_counts_against_the_rate_limit() {
+        let computer = FlightComputer::builder()
+            .command_port(free_port())
+            .log_port(free_port())
+            .command_rate_limit(3.0)
+            .build();
+        let command_addr = (computer.bind_addr, computer.command_port);
+        tokio::spawn(computer.run());
+        let mut client = commands(command_addr).await;
+        let status = r#"{"type":"status"}"#;
+
+        let oversized = send(&mut client, &format!("[{0},{0},{0},{0}]", status)).await;
+        let fitting = send(&mut client, &format!("[{0},{0},{0}]", status)).await;
+
+        assert_eq!(
+            oversized,
+            r#"{"status":"rejected","reason":"rate limited"}"#
+        );
+        assert_eq!(
+            fitting,
+            format!("[{0},{0},{0}]", r#"{"status":"accepted"}"#)
+        );
+    }
+
+    #[tokio::test]
+    async fn batch_is_handled_in_order_as_one_unit() {
+        let computer = FlightComputer::builder()
+            .command_port(free_port())
+            .log_port(free_port())
+            .build();
+        let command_addr = (computer.bind_addr, computer.command_port);
+        let mut log = connect(&computer.telemetry_hub).await;
+        tokio::spawn(computer.run());
+        let mut client = commands(command_addr).await;
+        send(&mut client, r#"{"type":"schedule","delay":60.0}"#).await;
+
+        let ack = send(
+            &mut client,
+            r#"[{"type":"cancel"},{"type":"launch"},{"type":"schedule","delay":2.0}]"#,
+        )
+        .await;
+        let outcomes: Vec<CommandOutcome> = serde_json::from_str(&ack).unwrap();
+        assert_eq!(outcomes[0], CommandOutcome::accepted());
+        assert!(
+            matches!(&outcomes[1], CommandOutcome::Rejected { reason } if reason.starts_with("invalid command: unknown variant `launch`"))
+        );
+        assert_eq!(outcomes[2], CommandOutcome::Accepted { id: Some(2) });
+
+        send(&mut client, r#"{"type":"status"}"#).await;
+        let status = wait_for(&mut log, "Status").await;
+        assert!(
+            status.contains("Status: 1 pending [#2 'main' in "),
+            "{}",
+            status
+        );
+        assert!(status.contains("invalid=1"), "{}", status);
+    }
+
+    #[tokio::test]
+    async fn unparsable_lines_are_counted_as_invalid() {
+        let computer = FlightComputer::builder()
+            .command_port(free_port())
+            .log_port(free_port())
+            .build();
+        let command_addr = (computer.bind_addr, computer.command_port);
+        let mut log = connect(&computer.telemetry_hub).await;
+        tokio::spawn(computer.run());
+        let mut client = commands(command_addr).await;
+
+        let single = send(&mut client, "fire!").await;
+        let batch = send(&mut client, "[1.0,").await;
+
+        assert!(
+            single.contains(r#""reason":"invalid command: "#),
+            "{}",
+            single
+        );
+        assert!(
+            batch.contains(r#""reason":"invalid command batch: "#),
+            "{}",
+            batch
+        );
+        assert!(
+            wait_for(&mut log, "[⚠️]")
+                .await
+                .contains("invalid command: ")
+        );
+        send(&mut client, r#"{"type":"status"}"#).await;
+        let status = wait_for(&mut log, "Status").await;
+        assert!(status.contains("invalid=2"), "{}", status);
+    }
+
+    #[tokio::test]
+    async fn supplied_processor_drives_its_actuator() {
+        let hub = TelemetryHub::new();
+        let actuator = RecordingActuator::default();
+        let processor =
+            CommandProcessor::new(hub.clone()).with_actuator(Arc::new(actuator.clone()));
+        let computer = FlightComputer::builder()
+            .command_port(free_port())
+            .log_port(free_port())
+            .telemetry_hub(hub)
+            .processor(processor)
+            .build();
+        let command_addr = (computer.bind_addr, computer.command_port);
+        let mut log = connect(&computer.telemetry_hub).await;
+        tokio::spawn(computer.run());
+        let mut client = commands(command_addr).await;
+
+        send(
+            &mut client,
+            r#"{"type":"schedule","delay":0.1,"stage":"aux"}"#,
+        )
+        .await;
+        wait_for(&mut log, "Firing").await;
+
+        assert_eq!(actuator.stages(), ["aux"]);
+    }
+
+    #[tokio::test]
+    async fn command_connections_are_logged_with_their_address() {
+        crate::log::tests::capture();
+        let computer = FlightComputer::new(free_port(), free_port());
+        let command_addr = (computer.bind_addr, computer.command_port);
+        let mut log = connect(&computer.telemetry_hub).await;
+        tokio::spawn(computer.run());
+        let client = connect_when_ready(command_addr).await;
+        let addr = client.local_addr().unwrap().to_string();
+        drop(client);
+        wait_for(&mut log, "Command client disconnected").await;
+
+        let events = crate::log::tests::captured();
+        let logged = |message: &str| {
+            events
+                .iter()
+                .any(|event| event.message == message && event.field("addr") == Some(&addr))
+        };
+        assert!(logged("command client connected"), "{:?}", events);
+        assert!(logged("command client disconnected"), "{:?}", events);
+    }
+
+    #[tokio::test]
+    async fn handle_reports_ephemeral_ports_and_shuts_down() {
+        let handle = FlightComputer::builder()
+            .command_port(0)
+            .log_port(0)
+            .build()
+            .start()
+            .await
+            .unwrap();
+        let (command_addr, log_addr) = (handle.command_addr(), handle.log_addr());
+        assert!(command_addr.port() != 0 && log_addr.port() != 0);
+        assert_ne!(command_addr.port(), log_addr.port());
+        let mut log = BufReader::new(TcpStream::connect(log_addr).await.unwrap()).lines();
+        let mut client = commands((command_addr.ip(), command_addr.port())).await;
+
+        send(&mut client, r#"{"type":"status"}"#).await;
+
+        assert!(wait_for(&mut log, "[📋]").await.contains("Status: idle"));
+        handle.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn run_returns_once_shutdown_resolves() {
+        let computer = FlightComputer::builder()
+            .command_port(0)
+            .log_port(0)
+            .build();
+        let (stop, stopped) = oneshot::channel::<()>();
+        let running = tokio::spawn(computer.run_with_shutdown(async {
+            let _ = stopped.await;
+        }));
+
+        stop.send(()).unwrap();
+
+        let result = tokio::time::timeout(Duration::from_secs(1), running)
+            .await
+            .expect("run did not return after shutdown");
+        assert!(result.unwrap().is_ok());
+    }
+
+    #[tokio::test]
+    async fn equal_ports_are_a_conflict() {
+        let result = FlightComputer::builder()
+            .command_port(9100)
+            .log_port(9100)
+            .build()
+            .start()
+            .await;
+
+        match result {
+            Err(FlightComputerError::PortConflict(port)) => assert_eq!(port, 9100),
+            Err(e) => panic!("expected PortConflict, got {}", e),
+            Ok(_) => panic!("started with equal ports"),
+        }
+        let run = FlightComputer::new(9100, 9100).run().await;
+        assert!(matches!(run, Err(FlightComputerError::PortConflict(9100))));
+    }
+
+    #[tokio::test]
+    async fn legacy_commands_are_echoed_in_canonical_form() {
+        let handle = FlightComputer::builder()
+            .command_port(0)
+            .log_port(0)
+            .echo_commands(true)
+            .build()
+            .start()
+            .await
+            .unwrap();
+        let command_addr = handle.command_addr();
+        let mut client = commands((command_addr.ip(), command_addr.port())).await;
+
+        let echo = send(&mut client, "2.0").await;
+        let ack = next_line(&mut client).await;
+        let batch_echo = send(&mut client, r#"[{"cancel":1},"bogus"]"#).await;
+
+        assert_eq!(echo, r#"{"echo":{"type":"schedule","delay":2.0}}"#);
+        assert_eq!(ack, r#"{"status":"accepted","id":1}"#);
+        assert_eq!(batch_echo, r#"{"echo":[{"type":"cancel","id":1},null]}"#);
+        handle.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn piped_commands_schedule_a_fire() {
+        let processor = Arc::new(Mutex::new(CommandProcessor::new(TelemetryHub::new())));
+        let (mut stdin, input) = tokio::io::duplex(1024);
+        let (output, stdout) = tokio::io::duplex(1024);
+        let reader = tokio::spawn(serve_command_client(
+            input,
+            output,
+            processor.clone(),
+            TokenBucket::new(DEFAULT_COMMAND_RATE_LIMIT),
+            None,
+            false,
+            DEFAULT_MAX_LINE_LENGTH,
+        ));
+
+        stdin.write_all(b"0.05\n").await.unwrap();
+        let mut acks = BufReader::new(stdout).lines();
+        let ack = acks.next_line().await.unwrap().unwrap();
+        assert_eq!(ack, r#"{"status":"accepted","id":1}"#);
+
+        let deadline = processor.lock().await.next_deadline().unwrap();
+        tokio::time::sleep_until(deadline).await;
+        processor.lock().await.tick().await;
+        assert_eq!(processor.lock().await.metrics().fires_executed, 1);
+        drop(stdin);
+        reader.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn pre_bound_listeners_are_served() {
+        let command_listener = std::net::TcpListener::bind((DEFAULT_BIND_ADDR, 0)).unwrap();
+        let log_listener = std::net::TcpListener::bind((DEFAULT_BIND_ADDR, 0)).unwrap();
+        let command_addr = command_listener.local_addr().unwrap();
+        let log_addr = log_listener.local_addr().unwrap();
+
+        let handle = FlightComputer::builder()
+            .command_listener(command_listener)
+            .log_listener(log_listener)
+            .build()
+            .start()
+            .await
+            .unwrap();
+        assert_eq!(
+            (handle.command_addr(), handle.log_addr()),
+            (command_addr, log_addr)
+        );
+
+        let mut log = BufReader::new(TcpStream::connect(log_addr).await.unwrap()).lines();
+        let mut client = commands((command_addr.ip(), command_addr.port())).await;
+        send(&mut client, r#"{"type":"status"}"#).await;
+        assert!(wait_for(&mut log, "[📋]").await.contains("Status: idle"));
+        handle.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn boot_message_reports_the_effective_config() {
+        let hub = TelemetryHub::new();
+        let processor = CommandProcessor::new(hub.clone()).with_delay_bounds(1.0, 120.0);
+        let handle = FlightComputer::builder()
+            .command_port(0)
+            .log_port(0)
+            .telemetry_hub(hub)
+            .processor(processor)
+            .tick_interval(Duration::from_millis(25))
+            .build()
+            .start()
+            .await
+            .unwrap();
+        let stream = TcpStream::connect(handle.log_addr()).await.unwrap();
+        let mut log = BufReader::new(stream).lines();
+
+        let boot = next_line(&mut log).await;
+        let (_, payload) = boot.split_once("[🚦] ").unwrap();
+        let config: serde_json::Value = serde_json::from_str(payload).unwrap();
+        assert_eq!(config["command_addr"], handle.command_addr().to_string());
+        assert_eq!(config["log_addr"], handle.log_addr().to_string());
+        assert_eq!(config["tick_interval_secs"], 0.025);
+        assert_eq!(
+            (
+                config["min_delay_secs"].as_f64(),
+                config["max_delay_secs"].as_f64()
+            ),
+            (Some(1.0), Some(120.0))
+        );
+        assert_eq!(config["auth_required"], false);
+        handle.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn over_long_line_closes_the_connection() {
+        let handle = FlightComputer::builder()
+            .command_port(0)
+            .log_port(0)
+            .max_line_length(1024)
+            .build()
+            .start()
+            .await
+            .unwrap();
+        let log_addr = handle.log_addr();
+        let command_addr = handle.command_addr();
+        let mut log = BufReader::new(TcpStream::connect(log_addr).await.unwrap()).lines();
+        let mut client = commands((command_addr.ip(), command_addr.port())).await;
+
+        let line = "x".repeat(4096);
+        client
+            .get_mut()
+            .get_mut()
+            .write_all(line.as_bytes())
+            .await
+            .unwrap();
+
+        let reason = "Command line exceeds 1024 bytes, closing connection";
+        assert_eq!(
+            next_line(&mut client).await,
+            format!(r#"{{"status":"rejected","reason":"{}"}}"#, reason)
+        );
+        assert!(client.next_line().await.unwrap().is_none());
+        assert!(wait_for(&mut log, "[⚠️]").await.ends_with(reason));
+        handle.shutdown().await.unwrap();
     }
 }
@@ -1,4 +1,9 @@
+pub mod actuator;
+pub mod client;
+pub mod clock;
 pub mod command;
 pub mod errors;
 pub mod flight_computer;
+pub mod log;
 pub mod telemetry;
+pub mod thrust;
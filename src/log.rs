@@ -0,0 +1,248 @@
+use std::{
+    fmt::{self, Display, Write as _},
+    io::Write as _,
+    sync::OnceLock,
+    time::SystemTime,
+};
+
+/// The name `RUST_LOG` directives use for this crate's events.
+const TARGET: &str = "prop_command_demo";
+
+/// How important a log event is, ordered from least to most verbose.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    /// Something failed and was not recovered.
+    Error,
+    /// Something unexpected happened that the flight computer recovered from.
+    Warn,
+    /// Routine operational events, such as connections and fires.
+    Info,
+    /// Detail that is only useful when diagnosing a problem.
+    Debug,
+}
+
+impl Level {
+    /// Parses a level name as used in `RUST_LOG`, ignoring case.
+    fn parse(name: &str) -> Option<Option<Self>> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "off" => Some(None),
+            "error" => Some(Some(Self::Error)),
+            "warn" => Some(Some(Self::Warn)),
+            "info" => Some(Some(Self::Info)),
+            "debug" | "trace" => Some(Some(Self::Debug)),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Error => "ERROR",
+            Self::Warn => "WARN",
+            Self::Info => "INFO",
+            Self::Debug => "DEBUG",
+        })
+    }
+}
+
+/// The most verbose level that is written, or `None` when logging is off.
+static MAX_LEVEL: OnceLock<Option<Level>> = OnceLock::new();
+
+/// Sets the log level from the `RUST_LOG` environment variable.
+///
+/// `RUST_LOG` holds comma-separated directives: a bare level such as `warn`
+/// applies to every event, and `prop_command_demo=debug` to this crate's;
+/// directives for other targets are ignored. Without `RUST_LOG`, or if
+/// nothing in it applies, `info` and above are written. Only the first call
+/// has an effect, and events logged before it use the default.
+pub fn init_from_env() {
+    let filter = std::env::var("RUST_LOG").unwrap_or_default();
+    let _ = MAX_LEVEL.set(parse_filter(&filter));
+}
+
+/// Parses `RUST_LOG` directives into the most verbose level to write.
+fn parse_filter(filter: &str) -> Option<Level> {
+    let mut level = Some(Level::Info);
+    for directive in filter.split(',') {
+        let parsed = match directive.split_once('=') {
+            Some((target, name)) if target.trim() == TARGET => Level::parse(name),
+            Some(_) => None,
+            None => Level::parse(directive),
+        };
+        if let Some(parsed) = parsed {
+            level = parsed;
+        }
+    }
+    level
+}
+
+/// Writes one event to stderr as `<timestamp> <LEVEL> <message> key=value...`,
+/// if its level is enabled.
+///
+/// Values holding whitespace, quotes or `=` are quoted so each line stays
+/// machine-parseable. This is called by the `error!`, `warn!`, `info!` and
+/// `debug!` macros rather than directly.
+///
+/// # Arguments
+///
+/// * `level` - The importance of the event.
+/// * `message` - A short, fixed description of what happened.
+/// * `fields` - The event's structured fields, by name.
+pub fn emit(level: Level, message: &str, fields: &[(&str, &dyn Display)]) {
+    #[cfg(test)]
+    tests::record(level, message, fields);
+    let max_level = *MAX_LEVEL.get_or_init(|| Some(Level::Info));
+    if max_level.is_none_or(|max| level > max) {
+        return;
+    }
+    let mut line = format!(
+        "{} {:5} {}",
+        crate::telemetry::format_utc(SystemTime::now()),
+        level,
+        message
+    );
+    for (key, value) in fields {
+        let value = value.to_string();
+        let plain = !value.is_empty()
+            && !value.contains(|c: char| c.is_whitespace() || c == '"' || c == '=');
+        if plain {
+            let _ = write!(line, " {}={}", key, value);
+        } else {
+            let _ = write!(line, " {}={:?}", key, value);
+        }
+    }
+    let _ = writeln!(std::io::stderr().lock(), "{}", line);
+}
+
+/// Logs an `Error` event, e.g. `error!("listener failed"; port = 8124, error = e)`.
+#[macro_export]
+macro_rules! error {
+    ($($event:tt)+) => {
+        $crate::log_event!($crate::log::Level::Error, $($event)+)
+    };
+}
+
+/// Logs a `Warn` event, e.g. `warn!("client dropped"; addr = addr)`.
+#[macro_export]
+macro_rules! warn {
+    ($($event:tt)+) => {
+        $crate::log_event!($crate::log::Level::Warn, $($event)+)
+    };
+}
+
+/// Logs an `Info` event, e.g. `info!("command client connected"; addr = addr)`.
+#[macro_export]
+macro_rules! info {
+    ($($event:tt)+) => {
+        $crate::log_event!($crate::log::Level::Info, $($event)+)
+    };
+}
+
+/// Logs a `Debug` event, e.g. `debug!("tick"; due = due)`.
+#[macro_export]
+macro_rules! debug {
+    ($($event:tt)+) => {
+        $crate::log_event!($crate::log::Level::Debug, $($event)+)
+    };
+}
+
+/// Expands a leveled macro's `message; key = value, ...` into a call to `emit`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! log_event {
+    ($level:expr, $message:expr $(; $($key:ident = $value:expr),+ $(,)?)?) => {
+        $crate::log::emit(
+            $level,
+            $message,
+            &[$($((stringify!($key), &$value as &dyn ::std::fmt::Display)),+)?],
+        )
+    };
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    /// A logged event, as seen by a capture.
+    #[derive(Debug, Clone, PartialEq)]
+    pub(crate) struct Event {
+        pub(crate) level: Level,
+        pub(crate) message: String,
+        pub(crate) fields: Vec<(String, String)>,
+    }
+
+    impl Event {
+        /// Returns the value of the field named `key`, if the event has one.
+        pub(crate) fn field(&self, key: &str) -> Option<&str> {
+            self.fields
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value.as_str())
+        }
+    }
+
+    thread_local! {
+        /// The events logged on this thread since `capture` was called.
+        static CAPTURED: RefCell<Option<Vec<Event>>> = const { RefCell::new(None) };
+    }
+
+    /// Starts capturing the events logged on the current thread.
+    ///
+    /// A `#[tokio::test]` runs its spawned tasks on the test's own thread, so
+    /// this sees every event the code under test logs, and nothing logged by
+    /// tests running alongside it.
+    pub(crate) fn capture() {
+        CAPTURED.with(|captured| *captured.borrow_mut() = Some(Vec::new()));
+    }
+
+    /// Returns the events captured so far on the current thread.
+    pub(crate) fn captured() -> Vec<Event> {
+        CAPTURED.with(|captured| captured.borrow().clone().unwrap_or_default())
+    }
+
+    /// Records an event for the current thread's capture, if one is running.
+    pub(super) fn record(level: Level, message: &str, fields: &[(&str, &dyn Display)]) {
+        CAPTURED.with(|captured| {
+            if let Some(events) = captured.borrow_mut().as_mut() {
+                events.push(Event {
+                    level,
+                    message: message.to_string(),
+                    fields: fields
+                        .iter()
+                        .map(|(key, value)| (key.to_string(), value.to_string()))
+                        .collect(),
+                });
+            }
+        });
+    }
+
+    #[test]
+    fn rust_log_directives_set_the_level() {
+        assert_eq!(parse_filter(""), Some(Level::Info));
+        assert_eq!(parse_filter("warn"), Some(Level::Warn));
+        assert_eq!(parse_filter("off"), None);
+        assert_eq!(
+            parse_filter("info,prop_command_demo=debug"),
+            Some(Level::Debug)
+        );
+        assert_eq!(parse_filter("hyper=debug"), Some(Level::Info));
+        assert_eq!(parse_filter("loud"), Some(Level::Info));
+    }
+
+    #[test]
+    fn events_carry_their_fields() {
+        capture();
+        crate::warn!("client dropped"; addr = "127.0.0.1:9000", attempts = 3);
+        crate::info!("ready");
+
+        let events = captured();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].level, Level::Warn);
+        assert_eq!(events[0].message, "client dropped");
+        assert_eq!(events[0].field("addr"), Some("127.0.0.1:9000"));
+        assert_eq!(events[0].field("attempts"), Some("3"));
+        assert!(events[1].fields.is_empty());
+    }
+}
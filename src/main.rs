@@ -1,7 +1,9 @@
 use prop_command_demo::{
-    errors::FlightComputerError, flight_computer::FlightComputer, telemetry::TelemetryHub,
+    command::CommandProcessor, error, errors::FlightComputerError, flight_computer::FlightComputer,
+    log, telemetry::TelemetryHub,
 };
-use tokio::signal;
+use std::path::PathBuf;
+use tokio::{signal, sync::oneshot};
 
 #[tokio::main]
 /// The main function initializes the `FlightComputer` and starts its main loop.
@@ -10,27 +12,53 @@ use tokio::signal;
 ///
 /// A `Result` indicating success or a `FlightComputerError`.
 async fn main() -> Result<(), FlightComputerError> {
-    // Initialize the FlightComputer with ports and telemetry hub
-    let fc = FlightComputer {
-        command_port: 8124,
-        log_port: 8125,
-        telemetry_hub: TelemetryHub::new().clone(),
-    };
+    // Log at the level `RUST_LOG` asks for, `info` by default
+    log::init_from_env();
+
+    // Initialize the FlightComputer with its default ports and telemetry hub,
+    // saving and restoring the schedule when started with `--persist <path>`
+    // and also reading commands from stdin when started with `--stdin`
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let persistence = args
+        .iter()
+        .position(|arg| arg == "--persist")
+        .and_then(|i| args.get(i + 1))
+        .map(PathBuf::from);
+    let read_stdin = args.iter().any(|arg| arg == "--stdin");
+    let telemetry_hub = TelemetryHub::new();
+    let processor = CommandProcessor::new(telemetry_hub.clone())
+        .with_persistence(persistence)
+        .await;
+    let fc = FlightComputer::builder()
+        .telemetry_hub(telemetry_hub)
+        .processor(processor)
+        .read_stdin(read_stdin)
+        .build();
 
     // Spawn the FlightComputer's main run loop as an asynchronous task
-    let fc_handle = tokio::spawn(fc.run());
+    let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+    let mut fc_handle = tokio::spawn(fc.run_with_shutdown(async {
+        let _ = shutdown_rx.await;
+    }));
 
-    // Wait for a Ctrl+C signal to gracefully shut down the application
-    signal::ctrl_c().await.map_err(FlightComputerError::Io)?;
+    // Wait for the FlightComputer to stop on its own, or for a Ctrl+C signal
+    // to gracefully shut it down
+    let joined = tokio::select! {
+        joined = &mut fc_handle => joined,
+        signal = signal::ctrl_c() => {
+            signal.map_err(FlightComputerError::Io)?;
+            let _ = shutdown_tx.send(());
+            fc_handle.await
+        }
+    };
 
-    // Wait for the FlightComputer task to finish and handle any errors
-    match fc_handle.await {
+    // Handle any errors from the FlightComputer task
+    match joined {
         Ok(Ok(())) => Ok(()), // The task completed successfully
         Ok(Err(e)) => Err(e), // The task returned an error
         Err(e) => {
-            eprintln!("FlightComputer task panicked: {:?}", e);
-            Err(FlightComputerError::Io(std::io::Error::new(
-                std::io::ErrorKind::Other,
+            error!("flight computer task panicked"; error = e);
+            Err(FlightComputerError::Io(std::io::Error::other(
                 "FlightComputer task panicked",
             )))
         }
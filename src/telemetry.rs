@@ -1,11 +1,437 @@
-use std::sync::Arc;
-use tokio::{io::AsyncWriteExt, net::TcpStream, sync::Mutex};
+use crate::errors::FlightComputerError;
+use crate::{error, info, warn};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::VecDeque,
+    fs::{self, File, OpenOptions},
+    future,
+    io::{self, Write},
+    net::{Ipv4Addr, Ipv6Addr, SocketAddr},
+    path::PathBuf,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+        mpsc::{self, SyncSender, TrySendError},
+    },
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpStream, UdpSocket},
+    sync::{Mutex, broadcast},
+    task::JoinSet,
+    time::{self, Instant},
+};
 
-/// A hub for managing telemetry data and sending it to a connected client.
+/// The wire format used for telemetry lines.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TelemetryFormat {
+    /// Human-readable `#seq [tag] payload` lines, or `[tag] payload` with
+    /// sequence numbers disabled.
+    #[default]
+    Text,
+    /// One JSON-encoded [`TelemetryMessage`] object per line.
+    Json,
+}
+
+/// How telemetry tags are rendered.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TagStyle {
+    /// The emoji tags used throughout the flight computer, e.g. `🚀`.
+    #[default]
+    Emoji,
+    /// Short ASCII tokens such as `FIRE`, keeping the whole stream 7-bit clean.
+    Ascii,
+}
+
+/// The tag a `CommandProcessor` sends with each kind of event, so telemetry
+/// can be localized or rebranded.
+///
+/// The defaults are the emoji used throughout the flight computer. The hub's
+/// `TagStyle::Ascii` mode still maps the default emoji to ASCII tokens, and
+/// passes custom ASCII tags through unchanged. The hub's own note about a
+/// dropped client is tagged separately, with `TelemetryHub::with_warning_tag`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TelemetryTags {
+    /// A fire was scheduled or restored, `🛰️ ⏳` by default.
+    pub schedule: String,
+    /// A countdown mark before ignition was reached, `⏳` by default.
+    pub countdown: String,
+    /// A propulsion fire ignited, `🚀` by default.
+    pub fire: String,
+    /// A thrust sample during a burn, `🔥` by default.
+    pub thrust: String,
+    /// A burn ran to completion, `🏁` by default.
+    pub burn_complete: String,
+    /// A fire was cancelled or a burn cut short, `🛑` by default.
+    pub cancel: String,
+    /// Everything was aborted, `🛑` by default.
+    pub abort: String,
+    /// The schedule was paused, `⏸️` by default.
+    pub pause: String,
+    /// The schedule was resumed, `▶️` by default.
+    pub resume: String,
+    /// A status report, `📋` by default.
+    pub status: String,
+    /// An abort or fault latch was cleared, `✅` by default.
+    pub cleared: String,
+    /// The processor was reset to idle, `🔄` by default.
+    pub reset: String,
+    /// A command was rejected or something went wrong, `⚠️` by default.
+    pub warning: String,
+}
+
+impl Default for TelemetryTags {
+    fn default() -> Self {
+        Self {
+            schedule: "🛰️ ⏳".to_string(),
+            countdown: "⏳".to_string(),
+            fire: "🚀".to_string(),
+            thrust: "🔥".to_string(),
+            burn_complete: "🏁".to_string(),
+            cancel: "🛑".to_string(),
+            abort: "🛑".to_string(),
+            pause: "⏸️".to_string(),
+            resume: "▶️".to_string(),
+            status: "📋".to_string(),
+            cleared: "✅".to_string(),
+            reset: "🔄".to_string(),
+            warning: "⚠️".to_string(),
+        }
+    }
+}
+
+/// The priority of a telemetry message, ordered from least to most urgent.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Routine events such as a scheduled fire.
+    #[default]
+    Info,
+    /// Unexpected but non-critical events such as an invalid command.
+    Warn,
+    /// Safety-relevant events such as a propulsion fire.
+    Critical,
+}
+
+/// A single telemetry message as emitted in [`TelemetryFormat::Json`] mode.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct TelemetryMessage {
+    /// The hub-wide sequence number, contiguous across messages so a consumer
+    /// can detect gaps.
+    #[serde(default)]
+    pub seq: u64,
+    /// The ISO-8601 UTC send time, absent when timestamps are disabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ts: Option<String>,
+    /// The priority of the message.
+    #[serde(default)]
+    pub severity: Severity,
+    /// A short tag describing the telemetry message.
+    pub tag: String,
+    /// The content of the telemetry message.
+    pub payload: String,
+}
+
+/// A telemetry client to register with a `TelemetryHub`, selecting its transport.
+#[derive(Debug)]
+pub enum TelemetryClient {
+    /// A connected TCP stream that receives a stream of newline-terminated lines.
+    Tcp(TcpStream),
+    /// A UDP destination that receives each message as a separate datagram.
+    Udp(SocketAddr),
+}
+
+impl From<TcpStream> for TelemetryClient {
+    fn from(stream: TcpStream) -> Self {
+        Self::Tcp(stream)
+    }
+}
+
+impl From<SocketAddr> for TelemetryClient {
+    fn from(addr: SocketAddr) -> Self {
+        Self::Udp(addr)
+    }
+}
+
+/// A registered client's connection, ready to send messages on.
+enum ClientSink {
+    /// A TCP stream.
+    Tcp(TcpStream),
+    /// A UDP socket connected to the client's address.
+    Udp(UdpSocket),
+}
+
+impl ClientSink {
+    /// Opens the connection used to send telemetry to `client`.
+    ///
+    /// UDP clients get their own socket connected to the destination, so the
+    /// kernel reports an unreachable client as a send error.
+    async fn open(client: TelemetryClient) -> io::Result<Self> {
+        match client {
+            TelemetryClient::Tcp(stream) => Ok(Self::Tcp(stream)),
+            TelemetryClient::Udp(addr) => {
+                let local: SocketAddr = match addr {
+                    SocketAddr::V4(_) => (Ipv4Addr::UNSPECIFIED, 0).into(),
+                    SocketAddr::V6(_) => (Ipv6Addr::UNSPECIFIED, 0).into(),
+                };
+                let socket = UdpSocket::bind(local).await?;
+                socket.connect(addr).await?;
+                Ok(Self::Udp(socket))
+            }
+        }
+    }
+
+    /// Sends one formatted telemetry message, failing with
+    /// `io::ErrorKind::TimedOut` if it cannot be written within `timeout`.
+    async fn send(&mut self, msg: &str, timeout: Duration) -> io::Result<()> {
+        let write = async {
+            match self {
+                Self::Tcp(stream) => stream.write_all(msg.as_bytes()).await,
+                Self::Udp(socket) => socket.send(msg.as_bytes()).await.map(|_| ()),
+            }
+        };
+        time::timeout(timeout, write).await.unwrap_or_else(|_| {
+            Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                "telemetry write timed out",
+            ))
+        })
+    }
+
+    /// Returns whether each message is sent as its own datagram, so a failed
+    /// send leaves the connection fit for the next one.
+    fn is_datagram(&self) -> bool {
+        matches!(self, Self::Udp(_))
+    }
+
+    /// Returns the address of the client.
+    fn peer_addr(&self) -> io::Result<SocketAddr> {
+        match self {
+            Self::Tcp(stream) => stream.peer_addr(),
+            Self::Udp(socket) => socket.peer_addr(),
+        }
+    }
+
+    /// Completes once the client closes its connection; UDP clients never do.
+    ///
+    /// Anything the client sends is discarded.
+    async fn closed(&mut self) {
+        match self {
+            Self::Tcp(stream) => {
+                let mut discard = [0; 256];
+                while stream.read(&mut discard).await.is_ok_and(|n| n > 0) {}
+            }
+            Self::Udp(_) => future::pending().await,
+        }
+    }
+
+    /// Closes the connection.
+    async fn close(self) {
+        if let Self::Tcp(mut stream) = self {
+            let _ = stream.shutdown().await;
+        }
+    }
+}
+
+/// The maximum number of messages buffered for the next client.
+const BACKLOG_CAPACITY: usize = 256;
+
+/// The default time after a client drops during which messages are kept for
+/// a client that reconnects: none, so replaying what a viewer missed is
+/// opt-in through `TelemetryHub::with_reconnect_window`.
+pub const DEFAULT_RECONNECT_WINDOW: Duration = Duration::ZERO;
+
+/// The default time a write to a telemetry client may take before it counts
+/// as a failed write.
+pub const DEFAULT_WRITE_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// The default number of consecutive failed sends after which a UDP telemetry
+/// client is dropped.
+pub const DEFAULT_FAILURE_THRESHOLD: u32 = 5;
+
+/// The number of messages a client may fall behind before it skips the oldest.
+const CHANNEL_CAPACITY: usize = 1024;
+
+/// The number of lines that may wait to be written to the log file before
+/// further lines are dropped.
+const LOG_FILE_CAPACITY: usize = 1024;
+
+/// The number of rotated log files kept, `.1` being the most recent.
+const ROTATED_LOG_FILES: u32 = 5;
+
+/// An on-disk telemetry log, rotated once it would grow past `max_bytes`.
+struct LogFile {
+    /// The path of the live log file.
+    path: PathBuf,
+    /// The size the live log file may reach before it is rotated.
+    max_bytes: u64,
+    /// The open live log file, if it has been opened.
+    file: Option<File>,
+    /// The current size of the live log file.
+    len: u64,
+}
+
+impl LogFile {
+    /// Appends every line received on `lines` until the hub is dropped.
+    fn run(mut self, lines: mpsc::Receiver<Arc<str>>) {
+        for line in lines {
+            if let Err(e) = self.append(&line) {
+                warn!("failed to write telemetry log"; path = self.path.display(), error = e);
+                // Reopen on the next line in case the file was moved or removed.
+                self.file = None;
+            }
+        }
+    }
+
+    /// Appends one line, first rotating the log if the line would take it
+    /// past `max_bytes`.
+    fn append(&mut self, line: &str) -> io::Result<()> {
+        if self.file.is_none() {
+            self.open()?;
+        }
+        if self.len > 0 && self.len + line.len() as u64 > self.max_bytes {
+            self.rotate()?;
+            self.open()?;
+        }
+        if let Some(file) = &mut self.file {
+            file.write_all(line.as_bytes())?;
+            self.len += line.len() as u64;
+        }
+        Ok(())
+    }
+
+    /// Opens the live log file for appending.
+    fn open(&mut self) -> io::Result<()> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        self.len = file.metadata()?.len();
+        self.file = Some(file);
+        Ok(())
+    }
+
+    /// Renames the live log to `.1`, shifting older logs up and discarding the
+    /// oldest beyond `ROTATED_LOG_FILES`.
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file = None;
+        for n in (1..ROTATED_LOG_FILES).rev() {
+            let from = self.rotated(n);
+            if from.exists() {
+                fs::rename(from, self.rotated(n + 1))?;
+            }
+        }
+        fs::rename(&self.path, self.rotated(1))
+    }
+
+    /// Returns the path of the `n`th rotated log, e.g. `telemetry.log.2`.
+    fn rotated(&self, n: u32) -> PathBuf {
+        let mut name = self.path.clone().into_os_string();
+        name.push(format!(".{}", n));
+        name.into()
+    }
+}
+
+/// An event broadcast from a `TelemetryHub` to every client task.
+#[derive(Debug, Clone)]
+enum HubEvent {
+    /// A formatted, newline-terminated telemetry message.
+    Message(Arc<str>),
+    /// Every client should close its connection once the messages before it are sent.
+    Disconnect,
+}
+
+/// A message kept to replay to the next client.
+struct Buffered {
+    /// The formatted, newline-terminated message.
+    msg: String,
+    /// Whether some client was connected to receive the message when it was sent.
+    delivered: bool,
+}
+
+/// The mutable state shared by every clone of a `TelemetryHub`.
+#[derive(Default)]
+struct HubState {
+    /// The tasks forwarding telemetry to each connected client.
+    clients: JoinSet<()>,
+    /// Messages kept for the next client, oldest first: those produced while
+    /// no client was connected, and those produced since a client dropped.
+    backlog: VecDeque<Buffered>,
+    /// When a client last dropped, if none has connected since.
+    dropped_at: Option<Instant>,
+    /// The boot message sent first to every new client, once one was sent.
+    boot: Option<String>,
+}
+
+impl HubState {
+    /// Keeps a message for the next client, dropping the oldest once the
+    /// backlog holds `BACKLOG_CAPACITY` messages.
+    fn buffer(&mut self, msg: String, delivered: bool) {
+        if self.backlog.len() == BACKLOG_CAPACITY {
+            self.backlog.pop_front();
+        }
+        self.backlog.push_back(Buffered { msg, delivered });
+    }
+
+    /// Returns whether a client dropped within the last `window`; never when
+    /// `window` is zero.
+    fn in_reconnect_window(&self, window: Duration) -> bool {
+        self.dropped_at.is_some_and(|at| at.elapsed() < window)
+    }
+
+    /// Closes the reconnection window once `window` has passed since a client
+    /// dropped, forgetting the delivered messages kept for a reconnecting
+    /// client so they are not replayed as new to a later one.
+    fn close_expired_window(&mut self, window: Duration) {
+        if self.dropped_at.is_some() && !self.in_reconnect_window(window) {
+            self.dropped_at = None;
+            self.backlog.retain(|buffered| !buffered.delivered);
+        }
+    }
+}
+
+/// A hub for managing telemetry data and fanning it out to every connected
+/// TCP client and registered UDP destination.
+///
+/// Messages are published on a broadcast channel, and each client is served
+/// by its own task that forwards from its subscription to the client's socket,
+/// so a slow client never holds up the producer or the other clients.
 #[derive(Clone)]
 pub struct TelemetryHub {
-    /// The telemetry client, wrapped in an `Arc<Mutex>` for thread-safe access.
-    client: Arc<Mutex<Option<TcpStream>>>,
+    /// The channel every message is published on.
+    sender: broadcast::Sender<HubEvent>,
+    /// The sequence number of the last message sent, shared by every clone.
+    sequence: Arc<AtomicU64>,
+    /// The shared hub state, wrapped in an `Arc<Mutex>` for thread-safe access.
+    state: Arc<Mutex<HubState>>,
+    /// Whether each message is prefixed with a UTC timestamp.
+    timestamps: bool,
+    /// Whether each text message is prefixed with its sequence number.
+    sequence_numbers: bool,
+    /// The wire format used for outgoing messages.
+    format: TelemetryFormat,
+    /// Messages below this severity are not sent to clients.
+    min_severity: Severity,
+    /// How tags are rendered in outgoing messages.
+    tag_style: TagStyle,
+    /// How long a write to one client may take before it counts as failed.
+    write_timeout: Duration,
+    /// How many consecutive sends to one UDP client may fail before it is dropped.
+    failure_threshold: u32,
+    /// The tag sent with the hub's own note when it drops a failing client.
+    warning_tag: String,
+    /// The queue of lines to append to the log file, if one is configured.
+    log_file: Option<SyncSender<Arc<str>>>,
+    /// How long after a client drops a reconnecting client is sent what it missed.
+    reconnect_window: Duration,
+}
+
+impl Default for TelemetryHub {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl TelemetryHub {
@@ -13,46 +439,1080 @@ impl TelemetryHub {
     ///
     /// # Returns
     ///
-    /// A new `TelemetryHub` with no connected client.
+    /// A new `TelemetryHub` with no connected clients that timestamps and
+    /// numbers its messages.
     pub fn new() -> Self {
         Self {
-            client: Arc::new(Mutex::new(None)),
+            sender: broadcast::channel(CHANNEL_CAPACITY).0,
+            sequence: Arc::new(AtomicU64::new(0)),
+            state: Arc::new(Mutex::new(HubState::default())),
+            timestamps: true,
+            sequence_numbers: true,
+            format: TelemetryFormat::default(),
+            min_severity: Severity::default(),
+            tag_style: TagStyle::default(),
+            write_timeout: DEFAULT_WRITE_TIMEOUT,
+            failure_threshold: DEFAULT_FAILURE_THRESHOLD,
+            warning_tag: TelemetryTags::default().warning,
+            log_file: None,
+            reconnect_window: DEFAULT_RECONNECT_WINDOW,
         }
     }
 
+    /// Enables or disables the UTC timestamp prefix on telemetry messages.
+    ///
+    /// Disabling it produces the `#seq [tag] payload` format, and the bare
+    /// `[tag] payload` format if sequence numbers are disabled too.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether messages should carry a timestamp.
+    pub fn with_timestamps(mut self, enabled: bool) -> Self {
+        self.timestamps = enabled;
+        self
+    }
+
+    /// Enables or disables the `#seq` prefix on text telemetry messages.
+    ///
+    /// Messages are numbered either way, and JSON messages always carry
+    /// their `seq`; this only keeps text lines in the legacy format.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether text messages should carry a sequence number.
+    pub fn with_sequence_numbers(mut self, enabled: bool) -> Self {
+        self.sequence_numbers = enabled;
+        self
+    }
+
+    /// Selects the wire format used for telemetry messages.
+    ///
+    /// # Arguments
+    ///
+    /// * `format` - The format to emit, `TelemetryFormat::Text` by default.
+    pub fn with_format(mut self, format: TelemetryFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Sets the minimum severity a message needs to be sent to clients.
+    ///
+    /// # Arguments
+    ///
+    /// * `severity` - The lowest severity that is still delivered, e.g. `Severity::Warn`
+    ///   to suppress routine `Info` messages.
+    pub fn with_min_severity(mut self, severity: Severity) -> Self {
+        self.min_severity = severity;
+        self
+    }
+
+    /// Selects whether tags are sent as emoji or as ASCII tokens.
+    ///
+    /// In `TagStyle::Ascii` mode a fire is reported as `[FIRE] Firing propulsion now!`,
+    /// and any other non-ASCII character in a message is replaced with `?`.
+    ///
+    /// # Arguments
+    ///
+    /// * `style` - The tag style to emit, `TagStyle::Emoji` by default.
+    pub fn with_tag_style(mut self, style: TagStyle) -> Self {
+        self.tag_style = style;
+        self
+    }
+
+    /// Sets how long a write to a single client may take.
+    ///
+    /// A client that does not accept a message within the timeout, for example
+    /// because it stopped reading, has that write counted as failed rather
+    /// than stalling its delivery or shutdown. A TCP client is dropped on its
+    /// first failed write, since the stream may hold part of the message.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - The longest a write may take, `DEFAULT_WRITE_TIMEOUT` by default.
+    pub fn with_write_timeout(mut self, timeout: Duration) -> Self {
+        self.write_timeout = timeout;
+        self
+    }
+
+    /// Sets how many consecutive sends to a single UDP client may fail before
+    /// the client is dropped.
+    ///
+    /// A successful send resets the count. TCP clients are dropped on their
+    /// first failed write, whatever the threshold, since a write that failed
+    /// or timed out part way leaves the stream unable to carry whole lines. When a client is dropped a single
+    /// Critical message, tagged as set by `with_warning_tag`, is sent to the
+    /// remaining clients, instead of reporting every failed write.
+    ///
+    /// # Arguments
+    ///
+    /// * `threshold` - The consecutive failures that trip the client,
+    ///   `DEFAULT_FAILURE_THRESHOLD` by default; `0` is treated as `1`.
+    pub fn with_failure_threshold(mut self, threshold: u32) -> Self {
+        self.failure_threshold = threshold;
+        self
+    }
+
+    /// Sets the tag of the note sent when a failing client is dropped, so it
+    /// can match a `CommandProcessor`'s `TelemetryTags::warning`.
+    ///
+    /// # Arguments
+    ///
+    /// * `tag` - The tag to use, `⚠️` by default.
+    pub fn with_warning_tag(mut self, tag: impl Into<String>) -> Self {
+        self.warning_tag = tag.into();
+        self
+    }
+
+    /// Tees every telemetry line to a log file, alongside the network clients.
+    ///
+    /// Lines are appended by a background thread, so file I/O never blocks a
+    /// send; if the thread falls `LOG_FILE_CAPACITY` lines behind, further
+    /// lines are dropped from the file. Once a line would take the file past
+    /// `max_bytes` it is rotated to `<path>.1`, shifting older logs to `.2`
+    /// and beyond, and a new file is started.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The log file to append to, created if it does not exist.
+    /// * `max_bytes` - The size at which the log file is rotated.
+    pub fn with_log_file(mut self, path: impl Into<PathBuf>, max_bytes: u64) -> Self {
+        let log = LogFile {
+            path: path.into(),
+            max_bytes,
+            file: None,
+            len: 0,
+        };
+        let (lines, received) = mpsc::sync_channel(LOG_FILE_CAPACITY);
+        thread::spawn(move || log.run(received));
+        self.log_file = Some(lines);
+        self
+    }
+
+    /// Sets how long after a client drops the messages it misses are kept.
+    ///
+    /// A client that connects within the window after another one dropped,
+    /// typically the same viewer reconnecting, is first sent every message
+    /// produced since the drop, even those other clients already received.
+    ///
+    /// # Arguments
+    ///
+    /// * `window` - How long messages are kept after a drop; zero, the
+    ///   `DEFAULT_RECONNECT_WINDOW`, disables the window.
+    pub fn with_reconnect_window(mut self, window: Duration) -> Self {
+        self.reconnect_window = window;
+        self
+    }
+
     /// Adds a telemetry client to the hub.
     ///
+    /// Previously connected clients keep receiving telemetry alongside the new one.
+    /// The new client is first sent the boot message, if one was sent. Any
+    /// messages buffered while no client was connected are then replayed to
+    /// it, in order, before it receives live telemetry, along with every
+    /// message since a client dropped if that was within the hub's
+    /// reconnection window. The client is then served by its own task until
+    /// it disconnects, a write to its TCP stream fails, or too many
+    /// consecutive sends to its UDP socket fail.
+    ///
+    /// The replay is written by the client's task rather than here, so a slow
+    /// client holds up neither the caller nor the senders waiting on the hub.
+    ///
     /// # Arguments
     ///
-    /// * `stream` - A `TcpStream` representing the client connection.
-    pub async fn add_client(&self, stream: TcpStream) {
-        *self.client.lock().await = Some(stream);
+    /// * `client` - A `TcpStream` representing the client connection, or a
+    ///   `SocketAddr` registering a UDP destination.
+    ///
+    /// # Returns
+    ///
+    /// A `FlightComputerError::TelemetryClientError` if the client could not be
+    /// reached, in which case the client is not added.
+    pub async fn add_client(
+        &self,
+        client: impl Into<TelemetryClient>,
+    ) -> Result<(), FlightComputerError> {
+        let sink = ClientSink::open(client.into())
+            .await
+            .map_err(FlightComputerError::TelemetryClientError)?;
+        // Taking the backlog and subscribing under one lock keeps a message
+        // from slipping between the replay and live telemetry.
+        let mut state = self.state.lock().await;
+        // Once the window is closed only undelivered messages remain.
+        state.close_expired_window(self.reconnect_window);
+        let mut replay = std::mem::take(&mut state.backlog);
+        if let Some(boot) = &state.boot {
+            replay.push_front(Buffered {
+                msg: boot.clone(),
+                delivered: true,
+            });
+        }
+        state.dropped_at = None;
+        while state.clients.try_join_next().is_some() {}
+        state
+            .clients
+            .spawn(forward(sink, replay, self.sender.subscribe(), self.clone()));
+        Ok(())
+    }
+
+    /// Shuts down and removes every connected telemetry client.
+    ///
+    /// Each client is sent the messages already published before its
+    /// connection is closed.
+    pub async fn disconnect_all(&self) {
+        let mut clients = std::mem::take(&mut self.state.lock().await.clients);
+        let _ = self.sender.send(HubEvent::Disconnect);
+        while clients.join_next().await.is_some() {}
+    }
+
+    /// Returns the number of currently connected telemetry clients.
+    pub async fn client_count(&self) -> usize {
+        self.sender.receiver_count()
     }
 
-    /// Sends a telemetry message to the connected client.
+    /// Sends an `Info` telemetry message to every connected client.
     ///
     /// # Arguments
     ///
     /// * `tag` - A short tag describing the telemetry message.
     /// * `payload` - The content of the telemetry message.
-    ///
-    /// If no client is connected, an error message is printed to the console.
     pub async fn send_telemetry(&self, tag: &str, payload: &str) {
-        let msg = format!("[{}] {}\n", tag, payload);
-        let mut guard = self.client.lock().await;
+        self.send_telemetry_with_severity(Severity::Info, tag, payload)
+            .await;
+    }
+
+    /// Sends a telemetry message of the given severity to every connected client.
+    ///
+    /// # Arguments
+    ///
+    /// * `severity` - The priority of the message.
+    /// * `tag` - A short tag describing the telemetry message.
+    /// * `payload` - The content of the telemetry message.
+    ///
+    /// Messages below the hub's minimum severity are discarded, and the rest
+    /// are numbered with the next sequence number, starting from 1, rendered
+    /// in the hub's tag style and published without waiting for
+    /// any client to receive them. A failed write to one client does not
+    /// prevent delivery to the others. A TCP client whose write fails, or a UDP
+    /// client whose sends fail the hub's failure threshold times in a row, is
+    /// removed from the hub so it is not retried, and a client that falls more than `CHANNEL_CAPACITY` messages behind skips
+    /// the oldest. Every message is also appended to the log file, if one is
+    /// configured. If no client is connected, the message is kept in a bounded
+    /// backlog, dropping the oldest message once it holds `BACKLOG_CAPACITY`
+    /// entries; the console is told once, when buffering starts. Within the
+    /// reconnection window after a client drops, delivered messages are kept
+    /// there too for a reconnecting client.
+    pub async fn send_telemetry_with_severity(&self, severity: Severity, tag: &str, payload: &str) {
+        if severity < self.min_severity {
+            return;
+        }
+        // Holding the lock keeps a message from slipping between a new
+        // client taking the backlog and subscribing, and keeps messages
+        // published in sequence order.
+        let mut state = self.state.lock().await;
+        state.close_expired_window(self.reconnect_window);
+        let (msg, delivered) = self.publish(severity, tag, payload);
+        if !delivered {
+            // Only report the start of buffering, not every message buffered.
+            if state.backlog.iter().all(|buffered| buffered.delivered) {
+                warn!("no telemetry client connected, buffering messages");
+            }
+            state.buffer(msg, false);
+        } else if state.in_reconnect_window(self.reconnect_window) {
+            state.buffer(msg, true);
+        }
+    }
+
+    /// Sends the boot message describing the system, which is also kept and
+    /// sent first to every client that connects later, ahead of any backlog.
+    ///
+    /// The boot message is an `Info` message that is never filtered by the
+    /// minimum severity. Sending another replaces the one kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `tag` - A short tag describing the telemetry message.
+    /// * `payload` - The content of the telemetry message.
+    pub async fn send_boot_telemetry(&self, tag: &str, payload: &str) {
+        let mut state = self.state.lock().await;
+        let (msg, _) = self.publish(Severity::Info, tag, payload);
+        state.boot = Some(msg);
+    }
+
+    /// Numbers, renders and publishes a message to the connected clients, and
+    /// appends it to the log file, if one is configured.
+    ///
+    /// Callers hold the state lock, so messages are published in sequence order.
+    ///
+    /// # Returns
+    ///
+    /// The formatted message, and whether any client was connected to receive it.
+    fn publish(&self, severity: Severity, tag: &str, payload: &str) -> (String, bool) {
+        let seq = self.sequence.fetch_add(1, Ordering::Relaxed) + 1;
+        let msg = match self.tag_style {
+            TagStyle::Emoji => self.format_message(seq, severity, tag, payload),
+            TagStyle::Ascii => {
+                self.format_message(seq, severity, ascii_tag(tag), &to_ascii(payload))
+            }
+        };
+        let line: Arc<str> = msg.as_str().into();
+        if let Some(log_file) = &self.log_file
+            && let Err(TrySendError::Full(_)) = log_file.try_send(line.clone())
+        {
+            warn!("telemetry log file is behind, dropping message");
+        }
+        let delivered = self.sender.send(HubEvent::Message(line)).is_ok();
+        (msg, delivered)
+    }
+
+    /// Records that a client dropped, opening the reconnection window after
+    /// closing any earlier one that has expired.
+    async fn client_dropped(&self) {
+        let mut state = self.state.lock().await;
+        state.close_expired_window(self.reconnect_window);
+        state.dropped_at = Some(Instant::now());
+    }
 
-        match guard.as_mut() {
-            Some(stream) => match stream.write_all(msg.as_bytes()).await {
-                Ok(_) => {
-                    // Telemetry sent successfully
+    /// Formats a newline-terminated telemetry line in the hub's format.
+    ///
+    /// In text mode this looks like
+    /// `2024-05-01T12:34:56.789Z #42 [🚀] Firing propulsion 'main' now!`.
+    fn format_message(&self, seq: u64, severity: Severity, tag: &str, payload: &str) -> String {
+        let ts = self.timestamps.then(|| format_utc(SystemTime::now()));
+        match self.format {
+            TelemetryFormat::Text => {
+                let mut line = String::new();
+                if let Some(ts) = ts {
+                    line.push_str(&ts);
+                    line.push(' ');
+                }
+                if self.sequence_numbers {
+                    line.push_str(&format!("#{} ", seq));
                 }
+                line.push_str(&format!("[{}] {}\n", tag, payload));
+                line
+            }
+            TelemetryFormat::Json => {
+                let message = TelemetryMessage {
+                    seq,
+                    ts,
+                    severity,
+                    tag: tag.to_string(),
+                    payload: payload.to_string(),
+                };
+                let mut line = serde_json::to_string(&message)
+                    .expect("telemetry message serialization cannot fail");
+                line.push('\n');
+                line
+            }
+        }
+    }
+}
+
+/// Replays `replay` to one client and then forwards every message published
+/// on `events` to it, until it disconnects, is tripped by a failed write, or
+/// the hub disconnects every client. A client lost in either of the first two
+/// ways opens the hub's reconnection window.
+///
+/// # Arguments
+///
+/// * `sink` - The client's connection.
+/// * `replay` - The boot message, if one was sent, then the messages buffered
+///   before the client connected, oldest first.
+/// * `events` - The client's subscription to the hub's channel.
+/// * `hub` - The hub the client belongs to, which reports a tripped client.
+async fn forward(
+    mut sink: ClientSink,
+    replay: VecDeque<Buffered>,
+    mut events: broadcast::Receiver<HubEvent>,
+    hub: TelemetryHub,
+) {
+    let peer = sink
+        .peer_addr()
+        .map_or_else(|_| "unknown".to_string(), |addr| addr.to_string());
+    for buffered in &replay {
+        if let Err(e) = sink.send(&buffered.msg, hub.write_timeout).await {
+            warn!("failed to replay telemetry, dropping client"; addr = peer, error = e);
+            return;
+        }
+    }
+    let mut failures = 0;
+    loop {
+        let event = tokio::select! {
+            event = events.recv() => event,
+            () = sink.closed() => {
+                info!("telemetry client disconnected"; addr = peer);
+                hub.client_dropped().await;
+                return;
+            }
+        };
+        match event {
+            Ok(HubEvent::Message(msg)) => match sink.send(&msg, hub.write_timeout).await {
+                Ok(()) => failures = 0,
                 Err(e) => {
-                    eprintln!("⚠️ Failed to send telemetry: {}", e);
+                    failures += 1;
+                    // A failed TCP write may have sent part of the line, so the
+                    // stream cannot carry another message; a datagram can.
+                    if !sink.is_datagram() || failures >= hub.failure_threshold.max(1) {
+                        // Unsubscribe first so the note is not queued for this client.
+                        drop(events);
+                        hub.client_dropped().await;
+                        error!("dropped telemetry client"; addr = peer, failures = failures, error = e);
+                        let msg = if failures == 1 {
+                            format!(
+                                "Dropped telemetry client {} after a failed write: {}",
+                                peer, e
+                            )
+                        } else {
+                            format!(
+                                "Dropped telemetry client {} after {} consecutive failed writes: {}",
+                                peer, failures, e
+                            )
+                        };
+                        hub.send_telemetry_with_severity(
+                            Severity::Critical,
+                            &hub.warning_tag,
+                            &msg,
+                        )
+                        .await;
+                        return;
+                    }
                 }
             },
-            None => {
-                eprintln!("⚠️ No telemetry client connected");
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                warn!("telemetry client fell behind"; skipped = skipped);
+            }
+            Ok(HubEvent::Disconnect) | Err(broadcast::error::RecvError::Closed) => break,
+        }
+    }
+    sink.close().await;
+}
+
+/// Maps an emoji telemetry tag to its ASCII token, e.g. `🚀` to `FIRE`.
+///
+/// Unknown tags are kept if they are already ASCII, and otherwise become `INFO`.
+fn ascii_tag(tag: &str) -> &str {
+    match tag {
+        "🚀" => "FIRE",
+        "🛑" => "STOP",
+        "⚠️" => "WARN",
+        "🛰️ ⏳" => "SCHED",
+        "⏳" => "COUNT",
+        "🏁" => "DONE",
+        "📋" => "STATUS",
+        "✅" => "OK",
+        "🔌" => "CONN",
+        "💓" => "BEAT",
+        "🔥" => "THRUST",
+        "🚦" => "BOOT",
+        "🔄" => "RESET",
+        "⏸️" => "HOLD",
+        "▶️" => "RESUME",
+        _ if tag.is_ascii() && !tag.is_empty() => tag,
+        _ => "INFO",
+    }
+}
+
+/// Replaces every non-ASCII character with `?`.
+fn to_ascii(text: &str) -> String {
+    text.chars()
+        .map(|c| if c.is_ascii() { c } else { '?' })
+        .collect()
+}
+
+/// Formats a time as an ISO-8601 UTC timestamp with millisecond precision.
+pub(crate) fn format_utc(time: SystemTime) -> String {
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let secs = since_epoch.as_secs() as i64;
+    let (year, month, day) = civil_from_days(secs.div_euclid(86_400));
+    let secs_of_day = secs.rem_euclid(86_400);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        secs_of_day / 3600,
+        secs_of_day % 3600 / 60,
+        secs_of_day % 60,
+        since_epoch.subsec_millis()
+    )
+}
+
+/// Converts days since the Unix epoch into a proleptic Gregorian `(year, month, day)`.
+///
+/// This is Howard Hinnant's `civil_from_days` algorithm.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::time::Duration;
+    use tokio::{
+        io::{AsyncBufReadExt, BufReader, Lines},
+        net::TcpListener,
+        time,
+    };
+
+    /// The telemetry lines a test client receives.
+    pub(crate) type Received = Lines<BufReader<TcpStream>>;
+
+    /// Connects a TCP telemetry client to `hub`, returning the lines it receives.
+    pub(crate) async fn connect(hub: &TelemetryHub) -> Received {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let client = TcpStream::connect(listener.local_addr().unwrap())
+            .await
+            .unwrap();
+        let (server, _) = listener.accept().await.unwrap();
+        hub.add_client(server).await.unwrap();
+        BufReader::new(client).lines()
+    }
+
+    /// Reads the next line, failing the test if none arrives within a second.
+    pub(crate) async fn next_line(lines: &mut Received) -> String {
+        time::timeout(Duration::from_secs(1), lines.next_line())
+            .await
+            .expect("timed out waiting for telemetry")
+            .unwrap()
+            .expect("telemetry connection closed")
+    }
+
+    /// Reads lines until one containing `needle` arrives, and returns it.
+    pub(crate) async fn wait_for(lines: &mut Received, needle: &str) -> String {
+        loop {
+            let line = next_line(lines).await;
+            if line.contains(needle) {
+                return line;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn every_client_receives_each_message() {
+        let hub = TelemetryHub::new()
+            .with_timestamps(false)
+            .with_sequence_numbers(false);
+        let mut first = connect(&hub).await;
+        let mut second = connect(&hub).await;
+
+        hub.send_telemetry("🚀", "Firing propulsion now!").await;
+
+        let expected = "[🚀] Firing propulsion now!";
+        assert_eq!(next_line(&mut first).await, expected);
+        assert_eq!(next_line(&mut second).await, expected);
+    }
+
+    #[tokio::test]
+    async fn dropped_client_is_pruned() {
+        let hub = TelemetryHub::new();
+        let client = connect(&hub).await;
+        assert_eq!(hub.client_count().await, 1);
+
+        // The first write after the peer closes can still succeed, so keep
+        // sending until one fails.
+        drop(client);
+        let pruned = async {
+            while hub.client_count().await > 0 {
+                hub.send_telemetry("🚀", "Firing propulsion now!").await;
+                time::sleep(Duration::from_millis(5)).await;
             }
+        };
+        time::timeout(Duration::from_secs(1), pruned)
+            .await
+            .expect("dropped client was not pruned");
+    }
+
+    #[tokio::test]
+    async fn timestamps_can_be_toggled() {
+        let stamped = TelemetryHub::new();
+        let mut stamped_client = connect(&stamped).await;
+        stamped.send_telemetry("🚀", "Firing").await;
+        let line = next_line(&mut stamped_client).await;
+        let (ts, rest) = line.split_once(' ').unwrap();
+        assert_eq!(rest, "#1 [🚀] Firing");
+        // e.g. 2024-05-01T12:34:56.789Z
+        assert_eq!(ts.len(), 24);
+        assert!(ts.ends_with('Z'));
+        assert_eq!((&ts[4..5], &ts[10..11], &ts[19..20]), ("-", "T", "."));
+
+        let bare = TelemetryHub::new()
+            .with_timestamps(false)
+            .with_sequence_numbers(false);
+        let mut bare_client = connect(&bare).await;
+        bare.send_telemetry("🚀", "Firing").await;
+        assert_eq!(next_line(&mut bare_client).await, "[🚀] Firing");
+    }
+
+    #[test]
+    fn format_utc_renders_iso_8601() {
+        let time = UNIX_EPOCH + Duration::from_millis(1_714_566_896_789);
+        assert_eq!(format_utc(time), "2024-05-01T12:34:56.789Z");
+        assert_eq!(format_utc(UNIX_EPOCH), "1970-01-01T00:00:00.000Z");
+    }
+
+    #[tokio::test]
+    async fn json_lines_parse_back_into_messages() {
+        let hub = TelemetryHub::new().with_format(TelemetryFormat::Json);
+        let mut client = connect(&hub).await;
+
+        hub.send_telemetry_with_severity(Severity::Critical, "🚀", "Firing")
+            .await;
+
+        let line = next_line(&mut client).await;
+        let message: TelemetryMessage = serde_json::from_str(&line).unwrap();
+        assert!(message.ts.is_some());
+        assert_eq!(message.severity, Severity::Critical);
+        assert_eq!(message.tag, "🚀");
+        assert_eq!(message.payload, "Firing");
+    }
+
+    #[tokio::test]
+    async fn messages_below_min_severity_are_suppressed() {
+        let hub = TelemetryHub::new()
+            .with_timestamps(false)
+            .with_min_severity(Severity::Warn);
+        let mut client = connect(&hub).await;
+
+        hub.send_telemetry("📋", "Status: idle").await;
+        hub.send_telemetry_with_severity(Severity::Warn, "⚠️", "Invalid command")
+            .await;
+
+        // Suppressed messages are not numbered, so no gap is reported.
+        assert_eq!(next_line(&mut client).await, "#1 [⚠️] Invalid command");
+    }
+
+    #[tokio::test]
+    async fn backlog_is_replayed_before_live_telemetry() {
+        let hub = TelemetryHub::new()
+            .with_timestamps(false)
+            .with_sequence_numbers(false);
+        for n in 1..=3 {
+            hub.send_telemetry("📋", &format!("buffered {}", n)).await;
         }
+
+        let mut client = connect(&hub).await;
+        hub.send_telemetry("📋", "live").await;
+
+        for expected in ["buffered 1", "buffered 2", "buffered 3", "live"] {
+            assert_eq!(next_line(&mut client).await, format!("[📋] {}", expected));
+        }
+    }
+
+    #[tokio::test]
+    async fn udp_destinations_receive_datagrams() {
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let hub = TelemetryHub::new()
+            .with_timestamps(false)
+            .with_sequence_numbers(false);
+        hub.add_client(socket.local_addr().unwrap()).await.unwrap();
+
+        hub.send_telemetry("🚀", "Firing").await;
+
+        let mut datagram = [0; 64];
+        let len = time::timeout(Duration::from_secs(1), socket.recv(&mut datagram))
+            .await
+            .expect("timed out waiting for a datagram")
+            .unwrap();
+        assert_eq!(&datagram[..len], "[🚀] Firing\n".as_bytes());
+    }
+
+    #[tokio::test]
+    async fn ascii_mode_is_seven_bit_clean() {
+        let hub = TelemetryHub::new().with_tag_style(TagStyle::Ascii);
+        let mut client = connect(&hub).await;
+
+        hub.send_telemetry("🚀", "Firing propulsion now! ✨").await;
+
+        let line = next_line(&mut client).await;
+        assert!(
+            line.ends_with("[FIRE] Firing propulsion now! ?"),
+            "{}",
+            line
+        );
+        assert!(line.is_ascii());
+    }
+
+    #[tokio::test]
+    async fn tcp_and_udp_subscribers_share_each_broadcast() {
+        let hub = TelemetryHub::new();
+        let mut tcp = connect(&hub).await;
+        let udp = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        hub.add_client(udp.local_addr().unwrap()).await.unwrap();
+        assert_eq!(hub.client_count().await, 2);
+
+        hub.send_telemetry("📋", "Status: idle").await;
+
+        let mut datagram = [0; 128];
+        let len = time::timeout(Duration::from_secs(1), udp.recv(&mut datagram))
+            .await
+            .expect("timed out waiting for a datagram")
+            .unwrap();
+        let line = next_line(&mut tcp).await;
+        assert_eq!(&datagram[..len], format!("{}\n", line).as_bytes());
+    }
+
+    #[tokio::test]
+    async fn replaying_to_a_stalled_client_blocks_nothing() {
+        let hub = TelemetryHub::new()
+            .with_timestamps(false)
+            .with_sequence_numbers(false);
+        let bulky = "x".repeat(64 * 1024);
+        for _ in 0..BACKLOG_CAPACITY {
+            hub.send_telemetry("📋", &bulky).await;
+        }
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let _stalled = TcpStream::connect(listener.local_addr().unwrap())
+            .await
+            .unwrap();
+        let (server, _) = listener.accept().await.unwrap();
+
+        time::timeout(Duration::from_secs(1), hub.add_client(server))
+            .await
+            .expect("adding a client waited on its replay")
+            .unwrap();
+        let mut live = connect(&hub).await;
+        time::timeout(Duration::from_secs(1), hub.send_telemetry("🚀", "Firing"))
+            .await
+            .expect("sending waited on a stalled client");
+
+        assert_eq!(next_line(&mut live).await, "[🚀] Firing");
+    }
+
+    #[tokio::test]
+    async fn client_that_never_reads_does_not_block_sends() {
+        let hub = TelemetryHub::new().with_write_timeout(Duration::from_millis(50));
+        let _stalled = connect(&hub).await;
+        let payload = "x".repeat(64 * 1024);
+
+        let started = time::Instant::now();
+        for _ in 0..200 {
+            hub.send_telemetry("🔥", &payload).await;
+        }
+        assert!(started.elapsed() < Duration::from_millis(500));
+
+        let dropped = async {
+            while hub.client_count().await > 0 {
+                time::sleep(Duration::from_millis(5)).await;
+            }
+        };
+        time::timeout(Duration::from_secs(2), dropped)
+            .await
+            .expect("stalled client was not dropped after its write timed out");
+    }
+
+    #[tokio::test]
+    async fn concurrent_sends_get_contiguous_sequence_numbers() {
+        let hub = TelemetryHub::new().with_format(TelemetryFormat::Json);
+        let mut client = connect(&hub).await;
+
+        let mut senders = JoinSet::new();
+        for _ in 0..4 {
+            let hub = hub.clone();
+            senders.spawn(async move {
+                for _ in 0..25 {
+                    hub.send_telemetry("📋", "tick").await;
+                }
+            });
+        }
+        senders.join_all().await;
+
+        for expected in 1..=100 {
+            let line = next_line(&mut client).await;
+            let message: TelemetryMessage = serde_json::from_str(&line).unwrap();
+            assert_eq!(message.seq, expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn text_lines_carry_their_sequence_number() {
+        let hub = TelemetryHub::new().with_timestamps(false);
+        let mut client = connect(&hub).await;
+
+        hub.send_telemetry("📋", "first").await;
+        hub.send_telemetry("📋", "second").await;
+
+        assert_eq!(next_line(&mut client).await, "#1 [📋] first");
+        assert_eq!(next_line(&mut client).await, "#2 [📋] second");
+    }
+
+    #[tokio::test]
+    async fn stalled_tcp_client_is_dropped_on_its_first_timed_out_write() {
+        let hub = TelemetryHub::new()
+            .with_format(TelemetryFormat::Json)
+            .with_write_timeout(Duration::from_millis(100))
+            .with_failure_threshold(3)
+            .with_warning_tag("ALERT");
+        let mut healthy = connect(&hub).await;
+        let _stalled = connect(&hub).await;
+        // Keep reading so only the stalled client's writes fail.
+        let notes = tokio::spawn(async move {
+            let mut notes = Vec::new();
+            loop {
+                let line = next_line(&mut healthy).await;
+                let message: TelemetryMessage = serde_json::from_str(&line).unwrap();
+                match message.payload.as_str() {
+                    "done" => return notes,
+                    payload if payload.starts_with("Dropped telemetry client") => {
+                        notes.push(message)
+                    }
+                    _ => {}
+                }
+            }
+        });
+
+        let payload = "x".repeat(64 * 1024);
+        let tripped = async {
+            while hub.client_count().await > 1 {
+                hub.send_telemetry("🔥", &payload).await;
+                time::sleep(Duration::from_millis(1)).await;
+            }
+        };
+        time::timeout(Duration::from_secs(5), tripped)
+            .await
+            .expect("stalled client was not dropped");
+        hub.send_telemetry("📋", "after").await;
+        hub.send_telemetry("📋", "done").await;
+
+        let notes = notes.await.unwrap();
+        assert_eq!(notes.len(), 1);
+        assert_eq!(notes[0].severity, Severity::Critical);
+        assert_eq!(notes[0].tag, "ALERT");
+        assert!(
+            notes[0]
+                .payload
+                .ends_with("after a failed write: telemetry write timed out"),
+            "{}",
+            notes[0].payload
+        );
+    }
+
+    #[tokio::test]
+    async fn failing_udp_client_is_tripped_once_after_consecutive_failures() {
+        crate::log::tests::capture();
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let hub = TelemetryHub::new()
+            .with_timestamps(false)
+            .with_sequence_numbers(false)
+            .with_failure_threshold(3);
+        hub.add_client(socket.local_addr().unwrap()).await.unwrap();
+        // Too large for one datagram, so every send of it fails while the
+        // socket stays usable for smaller messages.
+        let oversized = "x".repeat(70 * 1024);
+        let mut datagram = [0; 64];
+        let mut probe = async |hub: &TelemetryHub, socket: &UdpSocket| {
+            hub.send_telemetry("📋", "probe").await;
+            let len = time::timeout(Duration::from_secs(1), socket.recv(&mut datagram))
+                .await
+                .expect("client stopped receiving")
+                .unwrap();
+            assert_eq!(&datagram[..len], "[📋] probe\n".as_bytes());
+        };
+
+        // A successful send between failures resets the count.
+        for _ in 0..2 {
+            hub.send_telemetry("🔥", &oversized).await;
+        }
+        probe(&hub, &socket).await;
+        for _ in 0..2 {
+            hub.send_telemetry("🔥", &oversized).await;
+        }
+        probe(&hub, &socket).await;
+        assert_eq!(hub.client_count().await, 1);
+
+        for _ in 0..5 {
+            hub.send_telemetry("🔥", &oversized).await;
+        }
+        let dropped = async {
+            while hub.client_count().await > 0 {
+                time::sleep(Duration::from_millis(1)).await;
+            }
+        };
+        time::timeout(Duration::from_secs(1), dropped)
+            .await
+            .expect("failing client was not tripped");
+
+        let events = crate::log::tests::captured();
+        let trips: Vec<_> = events
+            .iter()
+            .filter(|event| event.message == "dropped telemetry client")
+            .collect();
+        assert_eq!(trips.len(), 1, "{:?}", events);
+        assert_eq!(trips[0].field("failures"), Some("3"));
+        // The note was published with no client left, so it waits in the backlog.
+        let mut late = connect(&hub).await;
+        let note = next_line(&mut late).await;
+        assert!(
+            note.starts_with("[⚠️] Dropped telemetry client 127.0.0.1:")
+                && note.contains("after 3 consecutive failed writes"),
+            "{}",
+            note
+        );
+    }
+
+    /// Waits until the file at `path` holds a line ending with `last`, and
+    /// returns its lines.
+    async fn log_lines(path: &std::path::Path, last: &str) -> Vec<String> {
+        let written = async {
+            loop {
+                let contents = fs::read_to_string(path).unwrap_or_default();
+                if contents.lines().any(|line| line.ends_with(last)) {
+                    return contents.lines().map(str::to_string).collect();
+                }
+                time::sleep(Duration::from_millis(5)).await;
+            }
+        };
+        time::timeout(Duration::from_secs(1), written)
+            .await
+            .expect("telemetry was not written to the log file")
+    }
+
+    #[tokio::test]
+    async fn log_file_receives_every_line() {
+        let dir = std::env::temp_dir().join(format!("prop-demo-{}-log", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("telemetry.log");
+        let hub = TelemetryHub::new()
+            .with_timestamps(false)
+            .with_log_file(&path, 1024 * 1024);
+
+        for n in 1..=5 {
+            hub.send_telemetry("📋", &format!("line {}", n)).await;
+        }
+
+        let lines = log_lines(&path, "line 5").await;
+        let _ = fs::remove_dir_all(&dir);
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[0], "#1 [📋] line 1");
+    }
+
+    #[tokio::test]
+    async fn log_file_rotates_past_its_cap() {
+        let dir = std::env::temp_dir().join(format!("prop-demo-{}-rotate", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("telemetry.log");
+        // Each line is 14 bytes, so two fit under the cap.
+        let hub = TelemetryHub::new()
+            .with_timestamps(false)
+            .with_sequence_numbers(false)
+            .with_log_file(&path, 30);
+
+        for n in 1..=5 {
+            hub.send_telemetry("📋", &format!("line {}", n)).await;
+        }
+
+        let live = log_lines(&path, "line 5").await;
+        let read = |n| fs::read_to_string(dir.join(format!("telemetry.log.{}", n))).unwrap();
+        let (first, second) = (read(1), read(2));
+        let _ = fs::remove_dir_all(&dir);
+        assert_eq!(live, ["[📋] line 5"]);
+        assert_eq!(first, "[📋] line 3\n[📋] line 4\n");
+        assert_eq!(second, "[📋] line 1\n[📋] line 2\n");
+    }
+
+    /// Waits until the hub has `count` clients.
+    async fn wait_for_clients(hub: &TelemetryHub, count: usize) {
+        let settled = async {
+            while hub.client_count().await != count {
+                time::sleep(Duration::from_millis(5)).await;
+            }
+        };
+        time::timeout(Duration::from_secs(1), settled)
+            .await
+            .expect("client count did not settle");
+    }
+
+    #[tokio::test]
+    async fn reconnecting_client_is_sent_what_it_missed() {
+        let hub = TelemetryHub::new()
+            .with_timestamps(false)
+            .with_sequence_numbers(false)
+            .with_reconnect_window(Duration::from_secs(5));
+        let mut watcher = connect(&hub).await;
+        let viewer = connect(&hub).await;
+
+        drop(viewer);
+        wait_for_clients(&hub, 1).await;
+        hub.send_telemetry("📋", "gap 1").await;
+        hub.send_telemetry("📋", "gap 2").await;
+        let mut reconnected = connect(&hub).await;
+        hub.send_telemetry("📋", "live").await;
+
+        for expected in ["gap 1", "gap 2", "live"] {
+            assert_eq!(
+                next_line(&mut reconnected).await,
+                format!("[📋] {}", expected)
+            );
+            assert_eq!(next_line(&mut watcher).await, format!("[📋] {}", expected));
+        }
+    }
+
+    #[tokio::test]
+    async fn delivered_messages_are_not_replayed_without_a_window() {
+        let hub = TelemetryHub::new()
+            .with_timestamps(false)
+            .with_sequence_numbers(false);
+        let _watcher = connect(&hub).await;
+        let viewer = connect(&hub).await;
+
+        drop(viewer);
+        wait_for_clients(&hub, 1).await;
+        hub.send_telemetry("📋", "seen by the watcher").await;
+        let mut reconnected = connect(&hub).await;
+        hub.send_telemetry("📋", "live").await;
+
+        assert_eq!(next_line(&mut reconnected).await, "[📋] live");
+    }
+
+    #[tokio::test]
+    async fn delivered_messages_are_forgotten_once_the_window_closes() {
+        let window = Duration::from_millis(100);
+        let hub = TelemetryHub::new()
+            .with_timestamps(false)
+            .with_sequence_numbers(false)
+            .with_reconnect_window(window);
+        let viewer = connect(&hub).await;
+        let watcher = connect(&hub).await;
+
+        drop(viewer);
+        wait_for_clients(&hub, 1).await;
+        hub.send_telemetry("📋", "seen by the watcher").await;
+        time::sleep(window * 2).await;
+        drop(watcher);
+        wait_for_clients(&hub, 0).await;
+        hub.send_telemetry("📋", "missed").await;
+        let mut reconnected = connect(&hub).await;
+
+        assert_eq!(next_line(&mut reconnected).await, "[📋] missed");
+    }
+
+    #[tokio::test]
+    async fn slow_client_does_not_delay_a_fast_one() {
+        let hub = TelemetryHub::new()
+            .with_format(TelemetryFormat::Json)
+            .with_write_timeout(Duration::from_secs(10));
+        let _slow = connect(&hub).await;
+        let mut fast = connect(&hub).await;
+
+        // Fill the slow client's socket so its next write blocks.
+        let payload = "x".repeat(64 * 1024);
+        for _ in 0..64 {
+            hub.send_telemetry("🔥", &payload).await;
+        }
+        hub.send_telemetry("📋", "marker").await;
+
+        let started = Instant::now();
+        let line = wait_for(&mut fast, "marker").await;
+        assert!(started.elapsed() < Duration::from_secs(1));
+        let message: TelemetryMessage = serde_json::from_str(&line).unwrap();
+        assert_eq!(message.payload, "marker");
+        assert_eq!(hub.client_count().await, 2);
     }
 }
@@ -0,0 +1,99 @@
+use std::time::Duration;
+
+/// The default time between thrust samples during a burn.
+pub const DEFAULT_SAMPLE_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Describes how thrust varies across a burn, for simulated thrust telemetry.
+///
+/// Thrust ramps linearly from zero to `peak_percent` over `ramp_up`, holds,
+/// and ramps linearly back to zero over the final `ramp_down` of the burn.
+/// When the ramps overlap on a short burn, thrust follows whichever is lower.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThrustProfile {
+    /// The thrust held between the ramps, as a percentage of full thrust.
+    pub peak_percent: f64,
+    /// How long thrust takes to rise from zero to the peak.
+    pub ramp_up: Duration,
+    /// How long thrust takes to fall from the peak to zero at the end of the burn.
+    pub ramp_down: Duration,
+    /// The time between thrust samples; zero is treated as one millisecond.
+    pub sample_interval: Duration,
+}
+
+impl Default for ThrustProfile {
+    /// A flat 100% thrust for the whole burn.
+    fn default() -> Self {
+        Self {
+            peak_percent: 100.0,
+            ramp_up: Duration::ZERO,
+            ramp_down: Duration::ZERO,
+            sample_interval: DEFAULT_SAMPLE_INTERVAL,
+        }
+    }
+}
+
+impl ThrustProfile {
+    /// Returns the thrust, as a percentage, at a point in a burn.
+    ///
+    /// # Arguments
+    ///
+    /// * `elapsed` - The time since ignition.
+    /// * `burn` - The total duration of the burn.
+    pub fn thrust_at(&self, elapsed: Duration, burn: Duration) -> f64 {
+        let elapsed = elapsed.min(burn);
+        let rising = ramp_fraction(elapsed, self.ramp_up);
+        let falling = ramp_fraction(burn - elapsed, self.ramp_down);
+        self.peak_percent * rising.min(falling)
+    }
+}
+
+/// Returns how far along a linear ramp of length `ramp` the time `t` is, from
+/// `0.0` to `1.0`; a zero-length ramp is always complete.
+fn ramp_fraction(t: Duration, ramp: Duration) -> f64 {
+    if ramp.is_zero() {
+        1.0
+    } else {
+        (t.as_secs_f64() / ramp.as_secs_f64()).min(1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn linear_ramps_rise_hold_and_fall() {
+        let profile = ThrustProfile {
+            peak_percent: 80.0,
+            ramp_up: Duration::from_secs(1),
+            ramp_down: Duration::from_millis(500),
+            ..ThrustProfile::default()
+        };
+        let burn = Duration::from_secs(2);
+        let at = |millis| profile.thrust_at(Duration::from_millis(millis), burn);
+
+        assert_eq!(at(0), 0.0);
+        assert_eq!(at(500), 40.0);
+        assert_eq!(at(1000), 80.0);
+        assert_eq!(at(1500), 80.0);
+        assert_eq!(at(1750), 40.0);
+        assert_eq!(at(2000), 0.0);
+        assert_eq!(at(5000), 0.0);
+    }
+
+    #[test]
+    fn overlapping_ramps_follow_the_lower() {
+        let profile = ThrustProfile {
+            ramp_up: Duration::from_secs(1),
+            ramp_down: Duration::from_secs(1),
+            ..ThrustProfile::default()
+        };
+        let burn = Duration::from_secs(1);
+
+        assert_eq!(profile.thrust_at(Duration::from_millis(500), burn), 50.0);
+        assert_eq!(
+            ThrustProfile::default().thrust_at(Duration::ZERO, burn),
+            100.0
+        );
+    }
+}